@@ -145,6 +145,6 @@ impl fmt::Display for AbilityScores {
             self.get(Abilities::Charisma),
             self.get_ability_modifier(Abilities::Charisma)
         )?;
-        writeln!(f, "")
+        writeln!(f)
     }
 }
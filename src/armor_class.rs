@@ -0,0 +1,203 @@
+use crate::{
+    ability::Abilities,
+    character::Character,
+    equipment::{Armor, ArmorType},
+};
+use std::fmt::{self, Formatter};
+
+/// Every source that adds up to the armor class, in the order applied
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArmorClassBreakdown {
+    pub parts: Vec<(String, i16)>,
+}
+
+impl ArmorClassBreakdown {
+    fn add(&mut self, source: impl Into<String>, value: i16) {
+        if value != 0 {
+            self.parts.push((source.into(), value));
+        }
+    }
+
+    pub fn total(&self) -> i16 {
+        self.parts
+            .iter()
+            .map(|(_, value)| value)
+            .sum::<i16>()
+            .max(1)
+    }
+}
+
+impl fmt::Display for ArmorClassBreakdown {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let parts: Vec<String> = self
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(index, (source, value))| match index {
+                0 => format!("{} {}", source, value),
+                _ => format!("{} {:+}", source, value),
+            })
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl Armor {
+    /// DEX modifier the armor lets through, heavy armor allows none
+    pub fn dex_bonus(&self, dex_modifier: i8) -> i8 {
+        match (self.armor_type, self.dex_bonus_max) {
+            (ArmorType::Heavy, _) => 0,
+            (_, Some(max)) => dex_modifier.min(max),
+            (_, None) => dex_modifier,
+        }
+    }
+}
+
+impl Character {
+    pub fn body_armor(&self) -> Option<&Armor> {
+        self.inventory.body_armor.as_ref()
+    }
+
+    pub fn shield(&self) -> Option<&Armor> {
+        self.inventory.shield.as_ref()
+    }
+
+    /// Worn armor and shield the character isn't proficient with. They still
+    /// count for AC, but STR and DEX rolls have disadvantage and no spells
+    /// can be cast.
+    pub fn armor_without_proficiency(&self) -> Vec<&Armor> {
+        let proficiencies = self.armor_proficiencies();
+        self.body_armor()
+            .into_iter()
+            .chain(self.shield())
+            .filter(|armor| !proficiencies.contains(&armor.armor_type))
+            .collect()
+    }
+
+    pub fn armor_class_breakdown(&self) -> ArmorClassBreakdown {
+        let mut breakdown = ArmorClassBreakdown::default();
+        let dex_modifier = self.stats.get_ability_modifier(Abilities::Dexterity);
+
+        match self.body_armor() {
+            Some(armor) => {
+                breakdown.add(armor.name.clone(), armor.base_ac as i16);
+                breakdown.add("DEX", armor.dex_bonus(dex_modifier) as i16);
+                breakdown.add(format!("{} magic", armor.name), armor.magic_bonus as i16);
+            }
+            None => {
+                breakdown.add("Unarmored", 10);
+                breakdown.add("DEX", dex_modifier as i16);
+                breakdown.add(
+                    "Unarmored Defense",
+                    self.unarmored_defense_bonus().unwrap_or(0),
+                );
+            }
+        }
+
+        if let Some(shield) = self.shield() {
+            breakdown.add(shield.name.clone(), shield.base_ac as i16);
+            breakdown.add(format!("{} magic", shield.name), shield.magic_bonus as i16);
+        }
+
+        breakdown.add("Defense", self.fighting_style_ac_bonus());
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attack::Grip,
+        class::Class,
+        equipment::Weapon,
+        inventory::Inventory,
+        test_support::{character_of, set_levels},
+    };
+
+    fn unarmored(class: Class, other: Abilities) -> Character {
+        let mut character = character_of(class);
+        character.inventory = Inventory::default();
+        character.stats.set(Abilities::Dexterity, 16);
+        character.stats.set(other, 14);
+        character
+    }
+
+    #[test]
+    fn monk_adds_wisdom_without_a_shield() {
+        let mut monk = unarmored(Class::Monk, Abilities::Wisdom);
+        assert_eq!(monk.calculate_armor_class(), 15);
+
+        monk.inventory.shield = Armor::get_armor("Shield");
+        assert_eq!(monk.calculate_armor_class(), 15);
+        assert_eq!(monk.unarmored_defense_bonus(), None);
+    }
+
+    #[test]
+    fn barbarian_adds_constitution_with_a_shield() {
+        let mut barbarian = unarmored(Class::Barbarian, Abilities::Constitution);
+        assert_eq!(barbarian.calculate_armor_class(), 15);
+
+        barbarian.inventory.shield = Armor::get_armor("Shield");
+        assert_eq!(barbarian.calculate_armor_class(), 17);
+
+        barbarian.inventory.body_armor = Armor::get_armor("Leather");
+        assert_eq!(barbarian.calculate_armor_class(), 16);
+    }
+
+    #[test]
+    fn martial_arts_die_and_dex_for_monk_weapons() {
+        let mut monk = unarmored(Class::Monk, Abilities::Wisdom);
+        let club = Weapon::get_weapon("Club").unwrap();
+
+        let attack = monk.weapon_attack(&club, Grip::MainHand);
+        assert_eq!(attack.ability, Abilities::Dexterity);
+        assert_eq!(attack.damage.dice_sides, 4);
+        assert_eq!(attack.damage.modifier, 3);
+
+        set_levels(&mut monk, &[(Class::Monk, 5)]);
+        assert_eq!(
+            monk.weapon_attack(&club, Grip::MainHand).damage.dice_sides,
+            6
+        );
+
+        monk.inventory.body_armor = Armor::get_armor("Leather");
+        let attack = monk.weapon_attack(&club, Grip::MainHand);
+        assert_eq!(attack.ability, Abilities::Strength);
+        assert_eq!(attack.damage.dice_sides, 4);
+    }
+
+    #[test]
+    fn worn_armor_caps_dexterity() {
+        let mut fighter = character_of(Class::Fighter);
+        fighter.inventory = Inventory::default();
+        fighter.fighting_styles.clear();
+        fighter.stats.set(Abilities::Dexterity, 16);
+
+        let mut worn = |name: &str| {
+            fighter.inventory.body_armor = Armor::get_armor(name);
+            fighter.calculate_armor_class()
+        };
+        assert_eq!(worn("Leather"), 14);
+        assert_eq!(worn("Scale Mail"), 16);
+        assert_eq!(worn("Chain Mail"), 16);
+    }
+
+    #[test]
+    fn shield_and_magic_bonuses_show_in_the_breakdown() {
+        let mut fighter = character_of(Class::Fighter);
+        fighter.inventory = Inventory::default();
+        fighter.fighting_styles.clear();
+        let mut chain_mail = Armor::get_armor("Chain Mail").unwrap();
+        chain_mail.magic_bonus = 1;
+        fighter.inventory.body_armor = Some(chain_mail);
+        fighter.inventory.shield = Armor::get_armor("Shield");
+
+        let breakdown = fighter.armor_class_breakdown();
+        assert_eq!(breakdown.total(), 19);
+        assert_eq!(
+            breakdown.to_string(),
+            "Chain Mail 16, Chain Mail magic +1, Shield +2"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use crate::{
+    ability::Abilities,
+    character::Character,
+    dice::Dice,
+    equipment::{DamageType, Weapon, WeaponProperty, WeaponRange},
+};
+use std::fmt::{self, Formatter};
+
+/// How a weapon is held when attacking with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grip {
+    MainHand,
+    TwoHands,
+    OffHand,
+}
+
+/// Attack roll and damage for one wielded weapon
+#[derive(Debug, Clone)]
+pub struct WeaponAttack {
+    pub weapon: String,
+    pub grip: Grip,
+    pub ability: Abilities,
+    pub proficient: bool,
+    pub attack_bonus: i8,
+    /// Damage dice with the damage modifier folded in
+    pub damage: Dice,
+    pub damage_type: DamageType,
+    /// Great Weapon Fighting
+    pub rerolls_low_dice: bool,
+    /// Normal and long range for ammunition and thrown weapons
+    pub range: Option<(u16, u16)>,
+}
+
+/// A d20 attack roll, at disadvantage the lower of two rolls counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackRoll {
+    pub rolls: [u8; 2],
+    pub disadvantage: bool,
+    pub total: i8,
+    pub critical: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttackError {
+    OutOfRange {
+        weapon: String,
+        distance: u16,
+        range: u16,
+    },
+}
+
+impl fmt::Display for Grip {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Grip::MainHand => write!(f, "Main hand"),
+            Grip::TwoHands => write!(f, "Two hands"),
+            Grip::OffHand => write!(f, "Off hand"),
+        }
+    }
+}
+
+impl fmt::Display for AttackError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AttackError::OutOfRange {
+                weapon,
+                distance,
+                range,
+            } => write!(
+                f,
+                "{} can't reach {} ft, its range is {} ft",
+                weapon, distance, range
+            ),
+        }
+    }
+}
+
+impl fmt::Display for AttackRoll {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.disadvantage {
+            write!(
+                f,
+                "Attack {} ({} and {} rolled, disadvantage)",
+                self.total, self.rolls[0], self.rolls[1]
+            )?;
+        } else {
+            write!(f, "Attack {} ({} rolled)", self.total, self.rolls[0])?;
+        }
+        if self.critical {
+            write!(f, " CRITICAL")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WeaponAttack {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<14} | {:<9} | {:+3} {} | {} {}",
+            self.weapon,
+            self.grip.to_string(),
+            self.attack_bonus,
+            self.ability,
+            self.damage,
+            self.damage_type
+        )?;
+        if let Some((normal, long)) = self.range {
+            write!(f, " ({}/{} ft)", normal, long)?;
+        }
+        if !self.proficient {
+            write!(f, " (not proficient)")?;
+        }
+        if self.rerolls_low_dice {
+            write!(f, " (reroll 1s and 2s)")?;
+        }
+        Ok(())
+    }
+}
+
+impl WeaponAttack {
+    #[allow(dead_code)]
+    /// Rolls the damage, a critical hit doubles the dice but not the
+    /// modifier. Great Weapon Fighting rerolls 1s and 2s once.
+    pub fn roll_damage(&self, critical: bool) -> i16 {
+        let mut dice = self.damage;
+        if critical {
+            dice.dice_count *= 2;
+        }
+        let rolled = if self.rerolls_low_dice {
+            dice.roll_total_rerolling(2)
+        } else {
+            dice.roll_total()
+        };
+        rolled.max(0)
+    }
+}
+
+impl Character {
+    /// Ranged weapons use DEX, finesse weapons the better of STR and DEX,
+    /// everything else STR unless Martial Arts allows DEX
+    pub fn weapon_ability(&self, weapon: &Weapon) -> Abilities {
+        let strength = self.stats.get_ability_modifier(Abilities::Strength);
+        let dexterity = self.stats.get_ability_modifier(Abilities::Dexterity);
+        let finesse = weapon.has_property(WeaponProperty::Finesse) && dexterity > strength;
+        if weapon.weapon_range == WeaponRange::Ranged || finesse {
+            Abilities::Dexterity
+        } else {
+            self.martial_arts_ability(weapon)
+        }
+    }
+
+    pub fn weapon_attack(&self, weapon: &Weapon, grip: Grip) -> WeaponAttack {
+        let ability = self.weapon_ability(weapon);
+        let modifier = self.stats.get_ability_modifier(ability);
+        let proficient = self.is_proficient_with(weapon);
+
+        let mut attack_bonus = modifier + self.fighting_style_attack_bonus(weapon);
+        if proficient {
+            attack_bonus += self.proficiency_bonus();
+        }
+
+        let two_handed_grip = grip == Grip::TwoHands;
+        let weapon_dice = if two_handed_grip {
+            weapon.versatile_damage_dice()
+        } else {
+            weapon.damage_dice()
+        };
+        let martial_arts_dice = self.martial_arts_damage_dice(weapon);
+        let max = |dice: Dice| dice.dice_count as u16 * dice.dice_sides as u16;
+        let mut damage = if max(martial_arts_dice) > max(weapon_dice) {
+            martial_arts_dice
+        } else {
+            weapon_dice
+        };
+
+        // The off hand only adds a negative modifier without Two-Weapon Fighting
+        let mut damage_modifier = match grip {
+            Grip::OffHand if !self.adds_modifier_to_off_hand_damage() => modifier.min(0),
+            _ => modifier,
+        };
+        // Dueling needs the other hand free of weapons
+        if grip == Grip::MainHand && self.inventory.off_hand.is_none() {
+            damage_modifier += self.fighting_style_damage_bonus(weapon, two_handed_grip);
+        }
+        damage.modifier = damage_modifier;
+
+        WeaponAttack {
+            weapon: weapon.name.clone(),
+            grip,
+            ability,
+            proficient,
+            attack_bonus,
+            damage,
+            damage_type: weapon.damage_type,
+            rerolls_low_dice: self.rerolls_low_damage_dice(weapon, two_handed_grip),
+            range: weapon.range(),
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Rolls an attack against a target `distance` feet away. Melee weapons
+    /// hit within reach, thrown and ranged weapons roll at disadvantage
+    /// between their normal and long range.
+    pub fn roll_weapon_attack(
+        &self,
+        weapon: &Weapon,
+        grip: Grip,
+        distance: u16,
+    ) -> Result<AttackRoll, AttackError> {
+        let in_reach = weapon.weapon_range == WeaponRange::Melee && distance <= weapon.reach();
+        let disadvantage = match weapon.range() {
+            _ if in_reach => false,
+            Some((normal, _)) if distance <= normal => false,
+            Some((_, long)) if distance <= long => true,
+            range => {
+                return Err(AttackError::OutOfRange {
+                    weapon: weapon.name.clone(),
+                    distance,
+                    range: range.map_or(weapon.reach(), |(_, long)| long),
+                });
+            }
+        };
+
+        let d20 = Dice::new(1, 20, 0);
+        let rolls = [d20.roll(), d20.roll()];
+        let roll = if disadvantage {
+            rolls[0].min(rolls[1])
+        } else {
+            rolls[0]
+        };
+        Ok(AttackRoll {
+            rolls,
+            disadvantage,
+            total: roll as i8 + self.weapon_attack(weapon, grip).attack_bonus,
+            critical: roll == 20,
+        })
+    }
+
+    /// Attacks with the main hand and off hand weapons
+    pub fn weapon_attacks(&self) -> Vec<WeaponAttack> {
+        let inventory = &self.inventory;
+        let main_grip = if inventory.two_handed_grip() {
+            Grip::TwoHands
+        } else {
+            Grip::MainHand
+        };
+        let mut attacks = Vec::new();
+        if let Some(weapon) = &inventory.main_hand {
+            attacks.push(self.weapon_attack(weapon, main_grip));
+        }
+        // Two-weapon fighting only works with a Light weapon in each hand
+        let light = |weapon: &Weapon| weapon.has_property(WeaponProperty::Light);
+        if let Some(weapon) = &inventory.off_hand
+            && light(weapon)
+            && inventory.main_hand.as_ref().is_none_or(light)
+        {
+            attacks.push(self.weapon_attack(weapon, Grip::OffHand));
+        }
+        attacks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::Class, fighting_style::FightingStyle, inventory::Inventory,
+        test_support::character_of,
+    };
+
+    fn weapon(name: &str) -> Weapon {
+        Weapon::get_weapon(name).unwrap()
+    }
+
+    #[test]
+    fn off_hand_attack_needs_two_light_weapons() {
+        let mut character = character_of(Class::Fighter);
+        character.inventory.shield = None;
+        character.inventory.main_hand = Some(weapon("Longsword"));
+        character.inventory.off_hand = Some(weapon("Dagger"));
+        let grips: Vec<Grip> = character
+            .weapon_attacks()
+            .iter()
+            .map(|attack| attack.grip)
+            .collect();
+        assert_eq!(grips, vec![Grip::MainHand]);
+
+        character.inventory.main_hand = Some(weapon("Shortsword"));
+        let grips: Vec<Grip> = character
+            .weapon_attacks()
+            .iter()
+            .map(|attack| attack.grip)
+            .collect();
+        assert_eq!(grips, vec![Grip::MainHand, Grip::OffHand]);
+    }
+
+    fn fighter_with(strength: u8, dexterity: u8) -> Character {
+        let mut character = character_of(Class::Fighter);
+        character.inventory = Inventory::default();
+        character.fighting_styles.clear();
+        character.stats.set(Abilities::Strength, strength);
+        character.stats.set(Abilities::Dexterity, dexterity);
+        character
+    }
+
+    #[test]
+    fn attack_uses_the_weapon_ability_and_proficiency() {
+        let fighter = fighter_with(16, 18);
+        let longsword = fighter.weapon_attack(&weapon("Longsword"), Grip::MainHand);
+        assert_eq!(longsword.ability, Abilities::Strength);
+        assert_eq!(longsword.attack_bonus, 5);
+        assert_eq!(longsword.damage.modifier, 3);
+        let rapier = fighter.weapon_attack(&weapon("Rapier"), Grip::MainHand);
+        assert_eq!(rapier.ability, Abilities::Dexterity);
+        assert_eq!(rapier.attack_bonus, 6);
+        let longbow = fighter.weapon_attack(&weapon("Longbow"), Grip::TwoHands);
+        assert_eq!(longbow.ability, Abilities::Dexterity);
+
+        let wizard = character_of(Class::Wizard);
+        let greataxe = wizard.weapon_attack(&weapon("Greataxe"), Grip::TwoHands);
+        assert!(!greataxe.proficient);
+        assert_eq!(greataxe.attack_bonus, 0);
+    }
+
+    #[test]
+    fn off_hand_drops_a_positive_modifier() {
+        let mut fighter = fighter_with(16, 10);
+        fighter.inventory.main_hand = Some(weapon("Shortsword"));
+        fighter.inventory.off_hand = Some(weapon("Handaxe"));
+        let off_hand = &fighter.weapon_attacks()[1];
+        assert_eq!(off_hand.grip, Grip::OffHand);
+        assert_eq!(off_hand.attack_bonus, 5);
+        assert_eq!(off_hand.damage.modifier, 0);
+
+        fighter.stats.set(Abilities::Strength, 8);
+        assert_eq!(fighter.weapon_attacks()[1].damage.modifier, -1);
+
+        fighter.stats.set(Abilities::Strength, 16);
+        fighter
+            .choose_fighting_style(FightingStyle::TwoWeaponFighting)
+            .unwrap();
+        assert_eq!(fighter.weapon_attacks()[1].damage.modifier, 3);
+    }
+
+    #[test]
+    fn sheet_lists_the_attacks() {
+        let mut fighter = fighter_with(10, 10);
+        fighter.inventory.main_hand = Some(weapon("Longsword"));
+        let sheet = fighter.to_string();
+        assert!(sheet.contains("ATTACKS"));
+        assert!(sheet.contains("Longsword"));
+    }
+
+    #[test]
+    fn long_range_rolls_at_disadvantage() {
+        let fighter = fighter_with(10, 10);
+        let longbow = weapon("Longbow");
+        let roll = fighter
+            .roll_weapon_attack(&longbow, Grip::TwoHands, 150)
+            .unwrap();
+        assert!(!roll.disadvantage);
+        let roll = fighter
+            .roll_weapon_attack(&longbow, Grip::TwoHands, 300)
+            .unwrap();
+        assert!(roll.disadvantage);
+        assert_eq!(roll.total, roll.rolls[0].min(roll.rolls[1]) as i8 + 2);
+        assert_eq!(
+            fighter.roll_weapon_attack(&longbow, Grip::TwoHands, 700),
+            Err(AttackError::OutOfRange {
+                weapon: "Longbow".to_string(),
+                distance: 700,
+                range: 600
+            })
+        );
+
+        let longsword = weapon("Longsword");
+        assert!(
+            fighter
+                .roll_weapon_attack(&longsword, Grip::MainHand, 5)
+                .is_ok()
+        );
+        assert_eq!(
+            fighter.roll_weapon_attack(&longsword, Grip::MainHand, 10),
+            Err(AttackError::OutOfRange {
+                weapon: "Longsword".to_string(),
+                distance: 10,
+                range: 5
+            })
+        );
+    }
+}
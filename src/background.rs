@@ -0,0 +1,61 @@
+use crate::skill::Skill;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Background {
+    Acolyte,
+    Charlatan,
+    Criminal,
+    Entertainer,
+    FolkHero,
+    GuildArtisan,
+    Hermit,
+    Noble,
+    Outlander,
+    Sage,
+    Sailor,
+    Soldier,
+    Urchin,
+}
+
+impl Background {
+    pub fn skill_proficiencies(&self) -> Vec<Skill> {
+        match self {
+            Background::Acolyte => vec![Skill::Insight, Skill::Religion],
+            Background::Charlatan => vec![Skill::Deception, Skill::SleightOfHand],
+            Background::Criminal => vec![Skill::Deception, Skill::Stealth],
+            Background::Entertainer => vec![Skill::Acrobatics, Skill::Performance],
+            Background::FolkHero => vec![Skill::AnimalHandling, Skill::Survival],
+            Background::GuildArtisan => vec![Skill::Insight, Skill::Persuasion],
+            Background::Hermit => vec![Skill::Medicine, Skill::Religion],
+            Background::Noble => vec![Skill::History, Skill::Persuasion],
+            Background::Outlander => vec![Skill::Athletics, Skill::Survival],
+            Background::Sage => vec![Skill::Arcana, Skill::History],
+            Background::Sailor => vec![Skill::Athletics, Skill::Perception],
+            Background::Soldier => vec![Skill::Athletics, Skill::Intimidation],
+            Background::Urchin => vec![Skill::SleightOfHand, Skill::Stealth],
+        }
+    }
+}
+
+impl fmt::Display for Background {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let background = match self {
+            Background::Acolyte => "Acolyte",
+            Background::Charlatan => "Charlatan",
+            Background::Criminal => "Criminal",
+            Background::Entertainer => "Entertainer",
+            Background::FolkHero => "Folk Hero",
+            Background::GuildArtisan => "Guild Artisan",
+            Background::Hermit => "Hermit",
+            Background::Noble => "Noble",
+            Background::Outlander => "Outlander",
+            Background::Sage => "Sage",
+            Background::Sailor => "Sailor",
+            Background::Soldier => "Soldier",
+            Background::Urchin => "Urchin",
+        };
+        write!(f, "{}", background)
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{
+    ability::Abilities, character::Character, dice::Dice, equipment::DamageType,
+    pact_magic::Invocation, spell::Spell, spellcasting::SpellcastingError,
+};
+use std::fmt::{self, Formatter};
+
+/// How the target of a spell gets to resist it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellResolution {
+    Attack { roll: u8, total: i8, critical: bool },
+    Save { ability: Abilities, dc: i8 },
+    Automatic,
+}
+
+/// Which kind of slot paid for a spell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotUsed {
+    Spell(u8),
+    Pact(u8),
+}
+
+#[derive(Debug, Clone)]
+pub struct CastResult {
+    pub spell: String,
+    /// `None` for cantrips
+    pub slot: Option<SlotUsed>,
+    pub resolution: SpellResolution,
+    pub damage: Option<(i16, DamageType)>,
+    pub healing: Option<i16>,
+    /// Concentration spell ended by casting this one
+    pub ended_concentration: Option<String>,
+}
+
+impl SlotUsed {
+    pub fn level(&self) -> u8 {
+        match self {
+            SlotUsed::Spell(level) | SlotUsed::Pact(level) => *level,
+        }
+    }
+}
+
+impl fmt::Display for SpellResolution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SpellResolution::Attack {
+                roll,
+                total,
+                critical,
+            } => {
+                write!(f, "Spell attack {} ({} rolled)", total, roll)?;
+                if *critical {
+                    write!(f, " CRITICAL")?;
+                }
+                Ok(())
+            }
+            SpellResolution::Save { ability, dc } => write!(f, "{} save DC {}", ability, dc),
+            SpellResolution::Automatic => write!(f, "No roll needed"),
+        }
+    }
+}
+
+impl fmt::Display for CastResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.spell)?;
+        match self.slot {
+            Some(SlotUsed::Spell(level)) => write!(f, " (level {} slot)", level)?,
+            Some(SlotUsed::Pact(level)) => write!(f, " (level {} pact slot)", level)?,
+            None => {}
+        }
+        write!(f, ": {}", self.resolution)?;
+        if let Some((amount, damage_type)) = self.damage {
+            write!(f, ", {} {} damage", amount, damage_type)?;
+        }
+        if let Some(amount) = self.healing {
+            write!(f, ", heals {}", amount)?;
+        }
+        if let Some(spell) = &self.ended_concentration {
+            write!(f, ", {} ends", spell)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cantrip damage dice multiply at character levels 5, 11 and 17
+pub fn cantrip_multiplier(character_level: u8) -> u8 {
+    match character_level {
+        0..=4 => 1,
+        5..=10 => 2,
+        11..=16 => 3,
+        _ => 4,
+    }
+}
+
+/// Rolls `base` plus `upcast` once per slot level above the spell's, with
+/// dice doubled on a critical hit
+fn roll_effect(base: Dice, upcast: Option<Dice>, levels_above: u8, critical: bool) -> i16 {
+    let crit = if critical { 2 } else { 1 };
+    let mut total = Dice::new(base.dice_count * crit, base.dice_sides, base.modifier).roll_total();
+    if let Some(upcast) = upcast {
+        for _ in 0..levels_above {
+            total += Dice::new(upcast.dice_count * crit, upcast.dice_sides, upcast.modifier)
+                .roll_total();
+        }
+    }
+    total.max(0)
+}
+
+impl Character {
+    /// Spellcasting ability of the class the spell is known or prepared through
+    pub fn casting_ability_for(&self, name: &str) -> Option<Abilities> {
+        self.classes.iter().find_map(|class_level| {
+            let spells = &class_level.spells;
+            let knows = spells
+                .cantrips
+                .iter()
+                .chain(&spells.known)
+                .chain(&spells.prepared)
+                .any(|known| known == name);
+            if !knows {
+                return None;
+            }
+            class_level.class.spellcasting_ability().or_else(|| {
+                class_level
+                    .subclass
+                    .and_then(|subclass| subclass.spellcasting_ability())
+            })
+        })
+    }
+
+    /// Lowest available slot of at least `level`, pact slots winning ties
+    fn find_slot(&self, level: u8) -> Option<SlotUsed> {
+        let spell_slot = (level.max(1)..=9)
+            .find(|slot_level| self.spell_slots.current[*slot_level as usize - 1] > 0)
+            .map(SlotUsed::Spell);
+        let pact_slot = (self.pact_slots.current > 0 && self.pact_slots.slot_level >= level)
+            .then_some(SlotUsed::Pact(self.pact_slots.slot_level));
+
+        match (spell_slot, pact_slot) {
+            (Some(spell), Some(pact)) if spell.level() < pact.level() => Some(spell),
+            (_, Some(pact)) => Some(pact),
+            (spell, None) => spell,
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Casts a known or prepared spell with a slot of `slot_level`, or the
+    /// next higher one available. Leveled spells spend the slot and add
+    /// their upcast dice, cantrips scale with character level.
+    pub fn cast_spell(
+        &mut self,
+        name: &str,
+        slot_level: u8,
+    ) -> Result<CastResult, SpellcastingError> {
+        let spells = Spell::database().map_err(|e| SpellcastingError::Database(e.to_string()))?;
+        let spell = spells
+            .get(name)
+            .ok_or_else(|| SpellcastingError::UnknownSpell(name.to_string()))?;
+        let ability = self
+            .casting_ability_for(name)
+            .ok_or_else(|| SpellcastingError::NotKnown(name.to_string()))?;
+        if let Some(armor) = self.armor_without_proficiency().first() {
+            return Err(SpellcastingError::ArmorNotProficient(armor.name.clone()));
+        }
+
+        let slot = if spell.is_cantrip() {
+            None
+        } else {
+            if slot_level < spell.level {
+                return Err(SpellcastingError::SlotBelowSpellLevel {
+                    spell_level: spell.level,
+                    slot_level,
+                });
+            }
+            let slot = self
+                .find_slot(slot_level)
+                .ok_or(SpellcastingError::NoSlot(slot_level))?;
+            match slot {
+                SlotUsed::Spell(level) => self.spell_slots.expend(level)?,
+                SlotUsed::Pact(_) => self.expend_pact_slot()?,
+            };
+            Some(slot)
+        };
+
+        let resolution = if spell.attack_roll {
+            let roll = Dice::new(1, 20, 0).roll();
+            SpellResolution::Attack {
+                roll,
+                total: roll as i8 + self.spell_attack_bonus(ability),
+                critical: roll == 20,
+            }
+        } else if let Some(save) = spell.save {
+            SpellResolution::Save {
+                ability: save,
+                dc: self.spell_save_dc(ability),
+            }
+        } else {
+            SpellResolution::Automatic
+        };
+        let critical = matches!(resolution, SpellResolution::Attack { critical: true, .. });
+        let levels_above = slot.map_or(0, |slot| slot.level() - spell.level);
+
+        let damage = match (spell.damage_dice(), spell.damage_type) {
+            (Some(mut dice), Some(damage_type)) => {
+                let mut bonus = 0;
+                if spell.is_cantrip() {
+                    let multiplier = cantrip_multiplier(self.level());
+                    dice.dice_count *= multiplier;
+                    // Agonizing Blast adds CHA to every beam
+                    if spell.name == "Eldritch Blast"
+                        && self.invocations.contains(&Invocation::AgonizingBlast)
+                    {
+                        bonus = self.stats.get_ability_modifier(Abilities::Charisma) as i16
+                            * multiplier as i16;
+                    }
+                }
+                let rolled = roll_effect(dice, spell.upcast_dice(), levels_above, critical);
+                Some(((rolled + bonus).max(0), damage_type))
+            }
+            _ => None,
+        };
+        let healing = spell.healing_dice().map(|dice| {
+            let rolled = roll_effect(dice, spell.upcast_dice(), levels_above, false);
+            (rolled + self.stats.get_ability_modifier(ability) as i16).max(0)
+        });
+
+        // Only one concentration spell can be active at a time
+        let ended_concentration = if spell.concentration {
+            self.start_concentration(&spell.name)
+        } else {
+            None
+        };
+
+        Ok(CastResult {
+            spell: spell.name.clone(),
+            slot,
+            resolution,
+            damage,
+            healing,
+            ended_concentration,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::Class,
+        spell_list::SpellList,
+        test_support::{character_of, set_levels},
+    };
+
+    fn wizard(level: u8) -> Character {
+        let mut wizard = character_of(Class::Wizard);
+        set_levels(&mut wizard, &[(Class::Wizard, level)]);
+        wizard.classes[0].spells = SpellList {
+            cantrips: vec!["Fire Bolt".to_string()],
+            prepared: vec!["Burning Hands".to_string()],
+            ..SpellList::default()
+        };
+        wizard
+    }
+
+    #[test]
+    fn upcast_spends_the_higher_slot_and_adds_dice() {
+        let mut wizard = wizard(3);
+        for _ in 0..20 {
+            wizard.spell_slots.current = wizard.spell_slots.max;
+            let result = wizard.cast_spell("Burning Hands", 2).unwrap();
+            assert_eq!(result.slot, Some(SlotUsed::Spell(2)));
+            assert_eq!(
+                result.resolution,
+                SpellResolution::Save {
+                    ability: Abilities::Dexterity,
+                    dc: 10
+                }
+            );
+            let (damage, damage_type) = result.damage.unwrap();
+            assert_eq!(damage_type, DamageType::Fire);
+            assert!((4..=24).contains(&damage));
+            assert_eq!(wizard.spell_slots.current[1], 1);
+        }
+    }
+
+    #[test]
+    fn casting_needs_a_slot_of_the_spell_level() {
+        let mut wizard = wizard(3);
+        assert_eq!(
+            wizard.cast_spell("Burning Hands", 3).err(),
+            Some(SpellcastingError::NoSlot(3))
+        );
+        assert_eq!(
+            wizard.cast_spell("Burning Hands", 0).err(),
+            Some(SpellcastingError::SlotBelowSpellLevel {
+                spell_level: 1,
+                slot_level: 0
+            })
+        );
+        assert_eq!(
+            wizard.cast_spell("Magic Missile", 1).err(),
+            Some(SpellcastingError::NotKnown("Magic Missile".to_string()))
+        );
+
+        // Out of 1st level slots, the next higher one is used
+        wizard.spell_slots.current[0] = 0;
+        let result = wizard.cast_spell("Burning Hands", 1).unwrap();
+        assert_eq!(result.slot, Some(SlotUsed::Spell(2)));
+        wizard.spell_slots.current[1] = 0;
+        assert_eq!(
+            wizard.cast_spell("Burning Hands", 1).err(),
+            Some(SpellcastingError::NoSlot(1))
+        );
+    }
+
+    #[test]
+    fn cantrips_scale_with_character_level() {
+        assert_eq!(cantrip_multiplier(4), 1);
+        assert_eq!(cantrip_multiplier(5), 2);
+        assert_eq!(cantrip_multiplier(11), 3);
+        assert_eq!(cantrip_multiplier(17), 4);
+
+        let mut wizard = wizard(5);
+        let slots = wizard.spell_slots.current;
+        for _ in 0..20 {
+            let result = wizard.cast_spell("Fire Bolt", 0).unwrap();
+            assert_eq!(result.slot, None);
+            let critical = matches!(
+                result.resolution,
+                SpellResolution::Attack { critical: true, .. }
+            );
+            let dice = if critical { 4 } else { 2 };
+            let (damage, _) = result.damage.unwrap();
+            assert!((dice..=dice * 10).contains(&damage));
+        }
+        assert_eq!(wizard.spell_slots.current, slots);
+    }
+}
@@ -1,31 +1,78 @@
 use crate::{
     ability::{Abilities, AbilityScores},
+    background::Background,
     class::Class,
-    equipment::{self, Armor, Weapon},
+    death_save::{DeathSaves, LifeState},
+    dice::Dice,
+    equipment::{DamageType, Weapon, WeaponProperty, WeaponRange, WeaponType},
     experience::XP_TABLE,
+    feature::Feature,
+    fighting_style::FightingStyle,
+    inventory::{Inventory, Item},
+    language::Language,
     money::{Money, MoneyType},
+    multiclass::ClassLevel,
+    pact_magic::{Invocation, PactBoon, PactSlots},
     race::Race,
-    skill::Skill,
+    resource::ResourcePool,
+    rest::HitDicePool,
+    skill::{Skill, SkillChoiceError},
+    spellcasting::SpellSlots,
+    subclass::{Subclass, SubclassError},
+    tool::Tool,
 };
-use serde::{Deserialize, Serialize};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Deserializer, Serialize, de};
 use std::fmt::{self, Formatter};
+use std::io;
+use strum::IntoEnumIterator;
 use strum_macros::{self, Display};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Character {
     pub name: String,
     pub race: Race,
-    pub class: Class,
-    pub level: u8,
+    #[serde(deserialize_with = "deserialize_classes")]
+    pub classes: Vec<ClassLevel>,
+    pub background: Background,
     pub current_xp: u32,
     pub stats: AbilityScores,
     pub max_hp: u16,
     pub current_hp: u16,
+    pub temp_hp: u16,
+    pub life_state: LifeState,
+    pub death_saves: DeathSaves,
+    pub hit_dice: Vec<HitDicePool>,
     pub skills: Vec<Skill>,
-    pub armor: Vec<Armor>,
-    pub weapons: Vec<Weapon>,
+    pub tools: Vec<Tool>,
+    pub languages: Vec<Language>,
+    pub resistances: Vec<DamageType>,
+    pub vulnerabilities: Vec<DamageType>,
+    pub immunities: Vec<DamageType>,
+    pub inventory: Inventory,
     pub current_load: f32,
     pub current_money: Vec<Money>,
+    pub resources: Vec<ResourcePool>,
+    pub fighting_styles: Vec<FightingStyle>,
+    pub spell_slots: SpellSlots,
+    pub pact_slots: PactSlots,
+    pub pact_boon: Option<PactBoon>,
+    pub invocations: Vec<Invocation>,
+    /// Concentration spell currently active
+    pub concentration: Option<String>,
+    /// Ability Score Improvements gained but not yet spent
+    pub ability_score_improvements: u8,
+}
+
+/// Saved characters need their starting class, `primary_class` relies on it
+fn deserialize_classes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ClassLevel>, D::Error> {
+    let classes = Vec::<ClassLevel>::deserialize(deserializer)?;
+    if classes.is_empty() {
+        return Err(de::Error::custom("a character needs at least one class"));
+    }
+    Ok(classes)
 }
 
 #[derive(Debug, Clone, Copy, Display)]
@@ -77,22 +124,38 @@ impl Character {
         }
     }
 
-    pub fn calculate_max_hp(&mut self) {
-        let base_hp = match &self.class {
-            Class::Barbarian => 12,
-            Class::Bard
-            | Class::Cleric
-            | Class::Druid
-            | Class::Monk
-            | Class::Rogue
-            | Class::Warlock => 8,
-            Class::Fighter | Class::Paladin | Class::Ranger => 10,
-            Class::Sorcerer | Class::Wizard => 6,
-        };
+    /// Total character level across all classes
+    pub fn level(&self) -> u8 {
+        self.classes
+            .iter()
+            .map(|class_level| class_level.level)
+            .sum()
+    }
 
-        let modifier = self.stats.get_ability_modifier(Abilities::Constitution);
+    /// The class the character started with, there is always one
+    pub fn primary_class(&self) -> Class {
+        self.classes[0].class
+    }
+
+    /// Levels taken in `class`, 0 when the character has none
+    pub fn class_level(&self, class: Class) -> u8 {
+        self.classes
+            .iter()
+            .find(|class_level| class_level.class == class)
+            .map(|class_level| class_level.level)
+            .unwrap_or(0)
+    }
+
+    pub fn calculate_max_hp(&mut self) {
+        let modifier = self.stats.get_ability_modifier(Abilities::Constitution) as i16;
 
-        self.max_hp = (base_hp + modifier).max(1) as u16
+        // Every level grants at least 1 hit point
+        self.max_hp = self
+            .classes
+            .iter()
+            .flat_map(|class_level| class_level.hp_rolls.iter())
+            .map(|roll| (*roll as i16 + modifier).max(1) as u16)
+            .sum();
     }
 
     fn calculate_carying_capacity(&self) -> u16 {
@@ -100,19 +163,267 @@ impl Character {
     }
 
     pub fn calculate_armor_class(&self) -> i16 {
-        let base_ac = 10;
-        let modifier = self.stats.get_ability_modifier(Abilities::Dexterity) as i16;
-        (base_ac + modifier).max(1) as i16
+        self.armor_class_breakdown().total()
+    }
+
+    pub fn wears_body_armor(&self) -> bool {
+        self.body_armor().is_some()
+    }
+
+    pub fn wears_shield(&self) -> bool {
+        self.shield().is_some()
+    }
+
+    /// Extra ability modifier from Unarmored Defense. Barbarians keep it
+    /// with a shield, Monks lose it. With both classes only one applies.
+    pub fn unarmored_defense_bonus(&self) -> Option<i16> {
+        if self.wears_body_armor() {
+            return None;
+        }
+        self.classes
+            .iter()
+            .filter(|class_level| !(class_level.class == Class::Monk && self.wears_shield()))
+            .filter_map(|class_level| class_level.class.unarmored_defense_ability())
+            .map(|ability| self.stats.get_ability_modifier(ability) as i16)
+            .max()
     }
 
     pub fn calculate_initiative(&self) -> i8 {
-        self.stats.get_ability_modifier(Abilities::Dexterity) as i8
+        self.stats.get_ability_modifier(Abilities::Dexterity)
     }
 
     pub fn proficiency_bonus(&self) -> i8 {
-        // In 5e, proficiency bonus is +2 at level 1, since we are creating initial chars,
-        // We'll hardcode this for now
-        2
+        // Driven by total character level: +2 at 1-4, +3 at 5-8 and so on
+        2 + (self.level().max(1) as i8 - 1) / 4
+    }
+
+    /// Skills granted outright by race and background
+    pub fn granted_skills(&self) -> Vec<Skill> {
+        let mut granted = self.race.skill_proficiencies();
+        for skill in self.background.skill_proficiencies() {
+            if !granted.contains(&skill) {
+                granted.push(skill);
+            }
+        }
+        granted
+    }
+
+    // A skill granted by both race and background frees up a pick of any other skill
+    fn overlapping_grants(&self) -> usize {
+        let race_skills = self.race.skill_proficiencies();
+        self.background
+            .skill_proficiencies()
+            .iter()
+            .filter(|skill| race_skills.contains(skill))
+            .count()
+    }
+
+    pub fn class_skill_count(&self) -> usize {
+        self.primary_class().skill_choice_count() + self.overlapping_grants()
+    }
+
+    #[allow(dead_code)]
+    /// Sets the class skill picks on top of the race and background skills.
+    /// A class skill already granted is replaced with another one from the
+    /// class list, only a skill granted by both race and background frees a
+    /// pick of any skill.
+    pub fn choose_class_skills(&mut self, picks: &[Skill]) -> Result<(), SkillChoiceError> {
+        let granted = self.granted_skills();
+        let allowed = self.primary_class().allowed_skills();
+
+        let expected = self.class_skill_count();
+        if picks.len() != expected {
+            return Err(SkillChoiceError::WrongCount {
+                expected,
+                got: picks.len(),
+            });
+        }
+
+        let mut free_picks = self.overlapping_grants();
+        let mut chosen: Vec<Skill> = Vec::new();
+
+        for skill in picks {
+            if granted.contains(skill) || chosen.contains(skill) {
+                return Err(SkillChoiceError::Duplicate(*skill));
+            }
+            if !allowed.contains(skill) {
+                if free_picks == 0 {
+                    return Err(SkillChoiceError::NotAllowed(*skill));
+                }
+                free_picks -= 1;
+            }
+            chosen.push(*skill);
+        }
+
+        self.skills = granted;
+        self.skills.extend(chosen);
+        Ok(())
+    }
+
+    /// Randomly picks the class skills, reproducible for the same seed
+    pub fn choose_random_class_skills(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let granted = self.granted_skills();
+
+        let mut picks: Vec<Skill> = self
+            .primary_class()
+            .allowed_skills()
+            .into_iter()
+            .filter(|skill| !granted.contains(skill))
+            .collect();
+        picks.shuffle(&mut rng);
+        picks.truncate(self.primary_class().skill_choice_count());
+
+        // Picks lost to duplicates are replaced with any skill not known yet
+        let mut others: Vec<Skill> = Skill::iter()
+            .filter(|skill| !granted.contains(skill) && !picks.contains(skill))
+            .collect();
+        others.shuffle(&mut rng);
+        let missing = self.class_skill_count() - picks.len();
+        picks.extend(others.into_iter().take(missing));
+
+        self.skills = granted;
+        self.skills.extend(picks);
+    }
+
+    pub fn choose_subclass(&mut self, subclass: Subclass) -> Result<(), SubclassError> {
+        let class = subclass.class();
+        let Some(class_level) = self.classes.iter_mut().find(|c| c.class == class) else {
+            return Err(SubclassError::WrongClass { subclass, class });
+        };
+        if let Some(chosen) = class_level.subclass {
+            return Err(SubclassError::AlreadyChosen(chosen));
+        }
+        let required = class.subclass_level();
+        if class_level.level < required {
+            return Err(SubclassError::LevelTooLow {
+                required,
+                current: class_level.level,
+            });
+        }
+
+        class_level.subclass = Some(subclass);
+        // Eldritch Knights and Arcane Tricksters start casting
+        self.refresh_spell_slots();
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    /// Subclass features unlocked at the current class levels
+    pub fn subclass_features(&self) -> Vec<Feature> {
+        self.classes
+            .iter()
+            .flat_map(|class_level| {
+                class_level
+                    .subclass
+                    .map(|subclass| subclass.features())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|feature| feature.level <= class_level.level)
+            })
+            .collect()
+    }
+
+    /// Class and subclass features at the current class levels. When a
+    /// feature improves at a later level only the latest entry of that class
+    /// is kept, the same feature from two classes (Extra Attack, Unarmored
+    /// Defense) is listed once per class.
+    pub fn features(&self) -> Vec<Feature> {
+        let mut unlocked: Vec<(Class, Feature)> = self
+            .classes
+            .iter()
+            .flat_map(|class_level| {
+                let subclass_features = class_level
+                    .subclass
+                    .map(|subclass| subclass.features())
+                    .unwrap_or_default();
+                class_level
+                    .class
+                    .features()
+                    .into_iter()
+                    .chain(subclass_features)
+                    .filter(|feature| feature.level <= class_level.level)
+                    .map(|feature| (class_level.class, feature))
+            })
+            .collect();
+        unlocked.sort_by_key(|(_, feature)| feature.level);
+
+        let mut features: Vec<(Class, Feature)> = Vec::new();
+        for (class, feature) in unlocked.into_iter().rev() {
+            if !features
+                .iter()
+                .any(|(known_class, known)| *known_class == class && known.name == feature.name)
+            {
+                features.push((class, feature));
+            }
+        }
+        features.reverse();
+        features.into_iter().map(|(_, feature)| feature).collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn rage_damage(&self) -> Option<u8> {
+        self.classes
+            .iter()
+            .find_map(|class_level| class_level.class.rage_damage(class_level.level))
+    }
+
+    #[allow(dead_code)]
+    pub fn sneak_attack_dice(&self) -> Option<Dice> {
+        self.classes
+            .iter()
+            .find_map(|class_level| class_level.class.sneak_attack_dice(class_level.level))
+    }
+
+    pub fn martial_arts_die(&self) -> Option<Dice> {
+        self.classes
+            .iter()
+            .find_map(|class_level| class_level.class.martial_arts_die(class_level.level))
+    }
+
+    /// Shortswords and simple melee weapons without the two-handed or heavy property
+    pub fn is_monk_weapon(weapon: &Weapon) -> bool {
+        weapon.name == "Shortsword"
+            || (weapon.weapon_type == WeaponType::Simple
+                && weapon.weapon_range == WeaponRange::Melee
+                && !weapon.is_two_handed()
+                && !weapon.has_property(WeaponProperty::Heavy))
+    }
+
+    /// Martial Arts only works unarmored and without a shield
+    fn uses_martial_arts(&self, weapon: &Weapon) -> bool {
+        self.martial_arts_die().is_some()
+            && !self.wears_body_armor()
+            && !self.wears_shield()
+            && Character::is_monk_weapon(weapon)
+    }
+
+    /// Ability used for attack and damage rolls with `weapon`, Martial Arts
+    /// allows DEX instead of STR for monk weapons
+    pub fn martial_arts_ability(&self, weapon: &Weapon) -> Abilities {
+        let strength = self.stats.get_ability_modifier(Abilities::Strength);
+        let dexterity = self.stats.get_ability_modifier(Abilities::Dexterity);
+        if self.uses_martial_arts(weapon) && dexterity > strength {
+            Abilities::Dexterity
+        } else {
+            Abilities::Strength
+        }
+    }
+
+    /// Damage dice for `weapon`, monk weapons roll the martial arts die when it is larger
+    pub fn martial_arts_damage_dice(&self, weapon: &Weapon) -> Dice {
+        let weapon_dice = weapon.damage_dice();
+        match self.martial_arts_die() {
+            Some(die) if self.uses_martial_arts(weapon) => {
+                let weapon_max = weapon_dice.dice_count as u16 * weapon_dice.dice_sides as u16;
+                if die.dice_sides as u16 > weapon_max {
+                    die
+                } else {
+                    weapon_dice
+                }
+            }
+            _ => weapon_dice,
+        }
     }
 
     pub fn skill_bonus(&self, skill: Skill) -> i8 {
@@ -127,17 +438,50 @@ impl Character {
         }
     }
 
+    pub fn saving_throw_proficiencies(&self) -> Vec<Abilities> {
+        let mut saves = self.primary_class().saving_throw_proficiencies();
+        let all = [
+            Abilities::Strength,
+            Abilities::Dexterity,
+            Abilities::Constitution,
+            Abilities::Intelligence,
+            Abilities::Wisdom,
+            Abilities::Charisma,
+        ];
+        // Slippery Mind and Diamond Soul
+        let mut extra = Vec::new();
+        if self.class_level(Class::Rogue) >= 15 {
+            extra.push(Abilities::Wisdom);
+        }
+        if self.class_level(Class::Monk) >= 14 {
+            extra.extend(all);
+        }
+        for ability in extra {
+            if !saves.contains(&ability) {
+                saves.push(ability);
+            }
+        }
+        saves
+    }
+
+    pub fn saving_throw_bonus(&self, ability: Abilities) -> i8 {
+        let ability_mod = self.stats.get_ability_modifier(ability);
+        if self.saving_throw_proficiencies().contains(&ability) {
+            ability_mod + self.proficiency_bonus()
+        } else {
+            ability_mod
+        }
+    }
+
     #[allow(dead_code)]
-    // get next level xp cap based on current xp
+    // get xp cap of the next total character level
     pub fn show_needed_xp(&self) -> u32 {
         XP_TABLE
-            .iter()
-            .find(|x| **x > self.current_xp)
+            .get(self.level() as usize + 1)
             .copied()
             .unwrap_or(355_000)
     }
 
-    #[allow(dead_code)]
     pub fn calculate_current_level(&self) -> u8 {
         XP_TABLE
             .iter()
@@ -149,168 +493,11 @@ impl Character {
     #[allow(dead_code)]
     //get xp needed to level up
     pub fn xp_needed_for_level_up(&self) -> u32 {
-        self.show_needed_xp() - &self.current_xp
+        self.show_needed_xp().saturating_sub(self.current_xp)
     }
 
     pub fn calculate_weight(&self) -> f32 {
-        let mut total: f32 = 0.00;
-        total += self.armor.iter().fold(0.0, |acc, x| acc + x.weight);
-        total += self.weapons.iter().fold(0.0, |acc, x| acc + x.weight);
-        total
-    }
-
-    fn set_class_default_armor(&mut self) {
-        let armor = match self.class {
-            Class::Barbarian => {
-                // Barbarians typically start unarmored or light armor
-                equipment::Armor::get_armor("Hide")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Bard => {
-                // Bards get leather armor
-                equipment::Armor::get_armor("Leather")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Cleric => {
-                // Clerics get scale mail or chain shirt
-                equipment::Armor::get_armor("Chain Mail")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Druid => {
-                // Druids get leather armor (no metal)
-                equipment::Armor::get_armor("Leather")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Fighter => {
-                // Fighters get chain mail or leather
-                equipment::Armor::get_armor("Chain Mail")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Monk => {
-                // Monks don't wear armor (unarmored defense)
-                vec![]
-            }
-            Class::Paladin => {
-                // Paladins get chain mail
-                equipment::Armor::get_armor("Chain Mail")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Ranger => {
-                // Rangers get scale mail or leather
-                equipment::Armor::get_armor("Scale Mail")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Rogue => {
-                // Rogues get leather armor
-                equipment::Armor::get_armor("Leather")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Sorcerer => {
-                // Sorcerers don't get armor proficiency
-                vec![]
-            }
-            Class::Warlock => {
-                // Warlocks get leather armor
-                equipment::Armor::get_armor("Leather")
-                    .map(|a| vec![a])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Wizard => {
-                // Wizards don't get armor proficiency
-                vec![]
-            }
-        };
-
-        self.armor = armor;
-    }
-
-    fn set_class_default_weapon(&mut self) {
-        let weapons = match self.class {
-            Class::Barbarian => {
-                // Barbarians get greataxe or martial melee weapon
-                equipment::Weapon::get_weapon("Greataxe")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Bard => {
-                // Bards get rapier or longsword or simple weapon
-                equipment::Weapon::get_weapon("Rapier")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Cleric => {
-                // Clerics get mace or warhammer (simple weapons)
-                equipment::Weapon::get_weapon("Mace")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Druid => {
-                // Druids get club, dagger, quarterstaff, etc.
-                equipment::Weapon::get_weapon("Quarterstaff")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Fighter => {
-                // Fighters get martial weapons - longsword and shield, or greatsword
-                equipment::Weapon::get_weapon("Longsword")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Monk => {
-                // Monks get shortsword or simple weapon
-                equipment::Weapon::get_weapon("Shortsword")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Paladin => {
-                // Paladins get martial weapons
-                equipment::Weapon::get_weapon("Longsword")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Ranger => {
-                // Rangers get two shortswords or two simple melee
-                if let Some(weapon) = equipment::Weapon::get_weapon("Shortsword") {
-                    vec![weapon.clone(), weapon] // Two shortswords
-                } else {
-                    vec![]
-                }
-            }
-            Class::Rogue => {
-                // Rogues get rapier or shortsword
-                equipment::Weapon::get_weapon("Rapier")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Sorcerer => {
-                // Sorcerers get light crossbow or simple weapon
-                equipment::Weapon::get_weapon("Light Crossbow")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Warlock => {
-                // Warlocks get light crossbow or simple weapon
-                equipment::Weapon::get_weapon("Light Crossbow")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-            Class::Wizard => {
-                // Wizards get quarterstaff or dagger
-                equipment::Weapon::get_weapon("Quarterstaff")
-                    .map(|w| vec![w])
-                    .unwrap_or_else(Vec::new)
-            }
-        };
-
-        self.weapons = weapons;
+        self.inventory.weight()
     }
 
     fn set_starter_money(&mut self) {
@@ -333,10 +520,7 @@ impl Character {
 
     //some fun
     pub fn calculate_look_cost(&self) -> f32 {
-        let mut total: f32 = 0.00;
-        total += self.armor.iter().fold(0.0, |acc, x| acc + x.price);
-        total += self.weapons.iter().fold(0.0, |acc, x| acc + x.price);
-        total
+        self.inventory.price()
     }
 
     fn calculate_encumberance(&self) -> (Encumberance, f32) {
@@ -356,21 +540,48 @@ impl Character {
         }
     }
 
-    pub fn new(name: String, race: Race, class: Class) -> Self {
+    /// Rolls up a new 1st level character. Fails when an item or spell
+    /// database in data/ can't be loaded.
+    pub fn new(
+        name: String,
+        race: Race,
+        class: Class,
+        background: Background,
+    ) -> Result<Self, io::Error> {
         let mut char = Character {
             name,
             race,
-            class,
-            level: 1,
+            classes: vec![ClassLevel::new(
+                class,
+                // 1st level HP is the maximum of the hit die
+                class.get_class_hit_dice().dice_sides,
+            )],
+            background,
             current_xp: 0,
             stats: AbilityScores::new(),
             max_hp: 0,
             current_hp: 0,
+            temp_hp: 0,
+            life_state: LifeState::Alive,
+            death_saves: DeathSaves::default(),
+            hit_dice: vec![],
             skills: vec![],
-            weapons: vec![],
-            armor: vec![],
+            tools: vec![],
+            languages: vec![],
+            resistances: race.damage_resistances(),
+            vulnerabilities: vec![],
+            immunities: vec![],
+            inventory: Inventory::default(),
             current_load: 0.0,
             current_money: vec![],
+            resources: vec![],
+            fighting_styles: vec![],
+            spell_slots: SpellSlots::default(),
+            pact_slots: PactSlots::default(),
+            pact_boon: None,
+            invocations: vec![],
+            concentration: None,
+            ability_score_improvements: 0,
         };
 
         char.apply_racial_bonuses();
@@ -378,47 +589,117 @@ impl Character {
         char.calculate_max_hp();
         char.current_hp = char.max_hp;
 
-        char.skills = char.class.get_class_skills();
+        char.choose_random_class_skills(rand::random());
+        char.choose_random_tools_and_languages(rand::random());
 
-        char.set_class_default_armor();
-        char.set_class_default_weapon();
+        // Clerics, Sorcerers and Warlocks pick their subclass at 1st level
+        if let Some(subclass) = class.subclasses().choose(&mut thread_rng()) {
+            let _ = char.choose_subclass(*subclass);
+        }
+
+        if let Some(style) = char.available_fighting_styles().choose(&mut thread_rng()) {
+            let _ = char.choose_fighting_style(*style);
+        }
+
+        let database = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        char.choose_random_starting_equipment(rand::random())
+            .map_err(|e| database(e.to_string()))?;
 
         char.set_starter_money();
 
-        char
+        char.refresh_resources();
+        char.refresh_hit_dice();
+        char.refresh_spell_slots();
+        char.refresh_pact_slots();
+        char.choose_random_spells(rand::random())
+            .map_err(|e| database(e.to_string()))?;
+        char.choose_random_pact_features(rand::random());
+
+        Ok(char)
     }
 }
 
 impl fmt::Display for Character {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        writeln!(f, "")?;
+        writeln!(f)?;
         writeln!(f, "═══════════════════════════════════════════════════")?;
         writeln!(
             f,
-            "    {}  •  {}  •  {}",
+            "    {}  •  {}  •  {}  •  {}",
             self.name.to_uppercase(),
             format!("{}", self.race).to_uppercase(),
-            format!("{}", self.class).to_uppercase()
+            self.classes
+                .iter()
+                .map(|class_level| {
+                    let mut class = class_level.class.to_string();
+                    if self.classes.len() > 1 {
+                        class = format!("{} {}", class, class_level.level);
+                    }
+                    if let Some(subclass) = class_level.subclass {
+                        class = format!("{} ({})", class, subclass);
+                    }
+                    class.to_uppercase()
+                })
+                .collect::<Vec<String>>()
+                .join(" / "),
+            format!("{}", self.background).to_uppercase()
         )?;
         writeln!(f, "═══════════════════════════════════════════════════")?;
-        writeln!(f, "")?;
+        writeln!(f)?;
 
         // Quick stats bar with emoji
         writeln!(
             f,
-            "❤  {}/{}    🛡 {}    ⚡ {:+}    ⭐ +{}",
+            "❤  {}/{}{}    🛡 {}    ⚡ {:+}    ⭐ +{}",
             self.current_hp,
             self.max_hp,
+            match self.temp_hp {
+                0 => String::new(),
+                temp_hp => format!(" (+{} temp)", temp_hp),
+            },
             self.calculate_armor_class(),
             self.calculate_initiative(),
             self.proficiency_bonus(),
         )?;
-        writeln!(f, "")?;
+        match self.life_state {
+            LifeState::Alive => {}
+            LifeState::Dying => writeln!(f, "   💀 Dying: {}", self.death_saves)?,
+            state => writeln!(f, "   💀 {}", state)?,
+        }
+        writeln!(f, "   AC: {}", self.armor_class_breakdown())?;
+        let unproficient: Vec<&str> = self
+            .armor_without_proficiency()
+            .iter()
+            .map(|armor| armor.name.as_str())
+            .collect();
+        if !unproficient.is_empty() {
+            writeln!(
+                f,
+                "   ⚠️  Not proficient with {}: disadvantage on STR and DEX rolls, no spellcasting",
+                unproficient.join(", ")
+            )?;
+        }
+        if !self.resistances.is_empty() {
+            let resistances: Vec<String> = self.resistances.iter().map(|d| d.to_string()).collect();
+            writeln!(f, "   Resistances: {}", resistances.join(", "))?;
+        }
+        if !self.vulnerabilities.is_empty() {
+            let vulnerabilities: Vec<String> =
+                self.vulnerabilities.iter().map(|d| d.to_string()).collect();
+            writeln!(f, "   Vulnerabilities: {}", vulnerabilities.join(", "))?;
+        }
+        if !self.immunities.is_empty() {
+            let immunities: Vec<String> = self.immunities.iter().map(|d| d.to_string()).collect();
+            writeln!(f, "   Immunities: {}", immunities.join(", "))?;
+        }
+        let hit_dice: Vec<String> = self.hit_dice.iter().map(|pool| pool.to_string()).collect();
+        writeln!(f, "   Hit Dice: {}", hit_dice.join(", "))?;
+        writeln!(f)?;
         writeln!(
             f,
             "XP  {}       🦸 {}    💪 {}/{} lbs {}",
             self.current_xp,
-            self.level,
+            self.level(),
             self.calculate_weight(),
             self.calculate_carying_capacity(),
             self.calculate_encumberance().0,
@@ -442,6 +723,146 @@ impl fmt::Display for Character {
         }
         writeln!(f, "═══════════════════════════════════════════════════")?;
 
+        // Proficiencies section
+        writeln!(f, "\n🗣️  PROFICIENCIES & LANGUAGES")?;
+        writeln!(f, "────────────────────")?;
+        let saves: Vec<String> = self
+            .saving_throw_proficiencies()
+            .iter()
+            .map(|ability| format!("{} {:+}", ability, self.saving_throw_bonus(*ability)))
+            .collect();
+        writeln!(f, "Saving Throws: {}", saves.join(", "))?;
+        let armor = self.armor_proficiencies();
+        if armor.is_empty() {
+            writeln!(f, "Armor: None")?;
+        } else {
+            let armor: Vec<String> = armor.iter().map(|a| a.to_string()).collect();
+            writeln!(f, "Armor: {}", armor.join(", "))?;
+        }
+        let weapons: Vec<String> = self
+            .weapon_proficiencies()
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        writeln!(f, "Weapons: {}", weapons.join(", "))?;
+        if self.tools.is_empty() {
+            writeln!(f, "Tools: None")?;
+        } else {
+            writeln!(f, "Tools:")?;
+            for tool in &self.tools {
+                writeln!(
+                    f,
+                    " • {} ({}) {:+}",
+                    tool,
+                    tool.ability(),
+                    self.tool_check_bonus(*tool)
+                )?;
+            }
+        }
+        let languages: Vec<String> = self.languages.iter().map(|l| l.to_string()).collect();
+        writeln!(f, "Languages: {}", languages.join(", "))?;
+        writeln!(f, "═══════════════════════════════════════════════════")?;
+
+        // Features section
+        writeln!(f, "\n📜 FEATURES")?;
+        writeln!(f, "────────────────────")?;
+        let features = self.features();
+        if features.is_empty() {
+            writeln!(f, "No class features")?;
+        } else {
+            for feature in &features {
+                writeln!(f, " • {}: {}", feature.name, feature.description)?;
+            }
+            for style in &self.fighting_styles {
+                writeln!(f, " • {}: {}", style, style.description())?;
+            }
+            if let Some(boon) = self.pact_boon {
+                writeln!(f, " • {}", boon)?;
+            }
+            for invocation in &self.invocations {
+                writeln!(f, " • {}: {}", invocation, invocation.description())?;
+            }
+        }
+        writeln!(f, "═══════════════════════════════════════════════════")?;
+
+        // Resources section
+        if !self.resources.is_empty() {
+            writeln!(f, "\n🔋 RESOURCES")?;
+            writeln!(f, "────────────────────")?;
+            for pool in &self.resources {
+                if pool.is_unlimited() {
+                    writeln!(f, " • {:<20} ∞ ({})", pool.kind.to_string(), pool.recovery)?;
+                } else {
+                    writeln!(
+                        f,
+                        " • {:<20} {}/{} ({})",
+                        pool.kind.to_string(),
+                        pool.current,
+                        pool.max,
+                        pool.recovery
+                    )?;
+                }
+            }
+            writeln!(f, "═══════════════════════════════════════════════════")?;
+        }
+
+        // Spellcasting section
+        let casters = self.spellcasting_classes();
+        if !casters.is_empty() {
+            writeln!(f, "\n🔮 SPELLCASTING")?;
+            writeln!(f, "────────────────────")?;
+            for (class, ability) in &casters {
+                writeln!(
+                    f,
+                    " • {:<10} ({})  Save DC {}  Attack {:+}",
+                    class.to_string(),
+                    ability,
+                    self.spell_save_dc(*ability),
+                    self.spell_attack_bonus(*ability)
+                )?;
+            }
+            if !self.spell_slots.is_empty() {
+                writeln!(f, " • Slots: {}", self.spell_slots)?;
+            }
+            if self.pact_slots.max > 0 {
+                writeln!(f, " • Pact Slots: {}", self.pact_slots)?;
+            }
+            if let Some(spell) = &self.concentration {
+                writeln!(f, " • Concentrating on {}", spell)?;
+            }
+            for class_level in &self.classes {
+                let spells = &class_level.spells;
+                let lists = [
+                    ("Cantrips", &spells.cantrips),
+                    ("Known", &spells.known),
+                    ("Spellbook", &spells.spellbook),
+                    ("Prepared", &spells.prepared),
+                ];
+                for (label, names) in lists {
+                    if !names.is_empty() {
+                        writeln!(f, " • {:<10} {}", label, names.join(", "))?;
+                    }
+                }
+            }
+            writeln!(f, "═══════════════════════════════════════════════════")?;
+        }
+
+        // Attacks section
+        let attacks = self.weapon_attacks();
+        if !attacks.is_empty() {
+            writeln!(f, "\n⚔️  ATTACKS")?;
+            writeln!(f, "────────────────────")?;
+            writeln!(
+                f,
+                "{:<14} | {:<9} | {:<7} | Damage",
+                "Weapon", "Grip", "Hit"
+            )?;
+            for attack in &attacks {
+                writeln!(f, "{}", attack)?;
+            }
+            writeln!(f, "═══════════════════════════════════════════════════")?;
+        }
+
         // Equipment section
         writeln!(f, "\n🛠️  EQUIPMENT")?;
         writeln!(f, "────────────────────")?;
@@ -458,10 +879,11 @@ impl fmt::Display for Character {
         }
         writeln!(f, "────────────────────")?;
 
-        if self.armor.is_empty() {
+        let inventory = &self.inventory;
+        if inventory.body_armor.is_none() && inventory.shield.is_none() {
             writeln!(f, "🛡️ No armor equipped")?;
         } else {
-            for armor in &self.armor {
+            for armor in inventory.body_armor.iter().chain(inventory.shield.iter()) {
                 writeln!(
                     f,
                     "🛡️  |{:<10} | {:^10} | {:<+4}",
@@ -470,15 +892,34 @@ impl fmt::Display for Character {
             }
         }
 
-        if self.weapons.is_empty() {
+        if inventory.main_hand.is_none() && inventory.off_hand.is_none() {
             writeln!(f, "⚔️  No weapon equipped")?;
         } else {
-            for weapon in &self.weapons {
-                writeln!(
-                    f,
-                    "⚔️  |{:<10} | {:^10} | {:<4} | {}",
-                    weapon.name, weapon.weapon_type, weapon.damage, weapon.damage_type,
-                )?;
+            let main_hand = if inventory.two_handed_grip() {
+                "Two hands"
+            } else {
+                "Main hand"
+            };
+            for (hand, weapon) in [
+                (main_hand, &inventory.main_hand),
+                ("Off hand", &inventory.off_hand),
+            ] {
+                if let Some(weapon) = weapon {
+                    writeln!(
+                        f,
+                        "⚔️  |{:<10} | {:^10} | {}",
+                        weapon.name, weapon.weapon_type, hand,
+                    )?;
+                }
+            }
+        }
+
+        for item in &inventory.carried {
+            match item {
+                Item::Gear(gear) if gear.quantity > 1 => {
+                    writeln!(f, "🎒  |{} ({})", gear.name, gear.quantity)?
+                }
+                item => writeln!(f, "🎒  |{}", item.name())?,
             }
         }
         writeln!(f, "═══════════════════════════════════════════════════")?;
@@ -499,3 +940,138 @@ impl fmt::Display for Character {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character, set_levels};
+
+    fn fighter(race: Race, background: Background) -> Character {
+        character(race, Class::Fighter, background)
+    }
+
+    #[test]
+    fn class_skills_come_from_the_class_list() {
+        let mut character = fighter(Race::Human, Background::Soldier);
+        assert_eq!(character.class_skill_count(), 2);
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Perception, Skill::Survival]),
+            Ok(())
+        );
+        assert_eq!(
+            character.skills,
+            vec![
+                Skill::Athletics,
+                Skill::Intimidation,
+                Skill::Perception,
+                Skill::Survival
+            ]
+        );
+    }
+
+    #[test]
+    fn class_skill_picks_are_checked() {
+        let mut character = fighter(Race::Human, Background::Sage);
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Athletics]),
+            Err(SkillChoiceError::WrongCount {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Athletics, Skill::Arcana]),
+            Err(SkillChoiceError::Duplicate(Skill::Arcana))
+        );
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Athletics, Skill::Athletics]),
+            Err(SkillChoiceError::Duplicate(Skill::Athletics))
+        );
+        // History comes from the background, the pick it would have used
+        // goes to another class skill rather than any skill
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Stealth, Skill::Athletics]),
+            Err(SkillChoiceError::NotAllowed(Skill::Stealth))
+        );
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Survival, Skill::Athletics]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn overlapping_race_and_background_skills_add_a_pick() {
+        // Intimidation comes from both, which frees one pick of any skill
+        let mut character = fighter(Race::HalfOrc, Background::Soldier);
+        assert_eq!(character.class_skill_count(), 3);
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Stealth, Skill::Arcana, Skill::Perception]),
+            Err(SkillChoiceError::NotAllowed(Skill::Arcana))
+        );
+        assert_eq!(
+            character.choose_class_skills(&[Skill::Stealth, Skill::Survival, Skill::Perception]),
+            Ok(())
+        );
+        assert_eq!(character.skills.len(), 5);
+    }
+
+    #[test]
+    fn random_class_skills_fill_every_pick() {
+        for seed in 0..20 {
+            let mut character = fighter(Race::HalfOrc, Background::Soldier);
+            character.choose_random_class_skills(seed);
+            let skills = &character.skills;
+            assert_eq!(skills.len(), 5);
+            for skill in skills {
+                assert_eq!(skills.iter().filter(|known| *known == skill).count(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn multiclass_features_are_kept_per_class() {
+        let mut character = fighter(Race::Human, Background::Soldier);
+        set_levels(
+            &mut character,
+            &[(Class::Fighter, 11), (Class::Barbarian, 5)],
+        );
+
+        let features = character.features();
+        let extra_attacks: Vec<u8> = features
+            .iter()
+            .filter(|feature| feature.name == "Extra Attack")
+            .map(|feature| feature.level)
+            .collect();
+        assert_eq!(extra_attacks, vec![5, 11]);
+        assert_eq!(
+            features
+                .iter()
+                .filter(|feature| feature.name == "Action Surge")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn improved_features_replace_earlier_entries() {
+        let mut character = fighter(Race::Human, Background::Soldier);
+        set_levels(&mut character, &[(Class::Fighter, 1)]);
+        let names: Vec<&str> = character.features().iter().map(|f| f.name).collect();
+        assert!(names.contains(&"Second Wind"));
+        assert!(!names.contains(&"Action Surge"));
+
+        set_levels(&mut character, &[(Class::Fighter, 17)]);
+        let features = character.features();
+        let action_surge: Vec<u8> = features
+            .iter()
+            .filter(|feature| feature.name == "Action Surge")
+            .map(|feature| feature.level)
+            .collect();
+        assert_eq!(action_surge, vec![17]);
+        assert!(
+            features
+                .windows(2)
+                .all(|pair| pair[0].level <= pair[1].level)
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use crate::{
+    ability::Abilities,
     dice::Dice,
-    equipment::{ArmorType, WeaponType},
+    equipment::{ArmorType, WeaponProficiency, WeaponType},
     skill::Skill,
 };
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Formatter};
+use strum::IntoEnumIterator;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Class {
     Barbarian,
     Bard,
@@ -23,32 +25,41 @@ pub enum Class {
 }
 
 impl Class {
-    #[allow(dead_code)]
     pub fn get_class_armor_proficiency(&self) -> Vec<ArmorType> {
         match self {
             Class::Barbarian => {
-                vec![ArmorType::Light, ArmorType::Medium]
+                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
             }
             Class::Bard => {
                 vec![ArmorType::Light]
             }
             Class::Cleric => {
-                vec![ArmorType::Light, ArmorType::Medium]
+                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
             }
             Class::Druid => {
-                vec![ArmorType::Light, ArmorType::Medium]
+                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
             }
             Class::Fighter => {
-                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Heavy]
+                vec![
+                    ArmorType::Light,
+                    ArmorType::Medium,
+                    ArmorType::Heavy,
+                    ArmorType::Shield,
+                ]
             }
             Class::Monk => {
                 vec![]
             }
             Class::Paladin => {
-                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Heavy]
+                vec![
+                    ArmorType::Light,
+                    ArmorType::Medium,
+                    ArmorType::Heavy,
+                    ArmorType::Shield,
+                ]
             }
             Class::Ranger => {
-                vec![ArmorType::Light, ArmorType::Medium]
+                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
             }
             Class::Rogue => {
                 vec![ArmorType::Light]
@@ -65,65 +76,179 @@ impl Class {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn get_weapon_proficieny(&self) -> Vec<WeaponType> {
+    /// Saving throws of the starting class, later classes don't grant any
+    pub fn saving_throw_proficiencies(&self) -> Vec<Abilities> {
         match self {
-            //for some classes the granularity of weapon proficiency is much detailed, but ...
-            Class::Barbarian => {
-                vec![WeaponType::Simple, WeaponType::Martial]
-            }
-            Class::Bard => {
-                vec![WeaponType::Simple]
-            }
-            Class::Cleric => {
-                vec![WeaponType::Simple]
+            Class::Barbarian | Class::Fighter => vec![Abilities::Strength, Abilities::Constitution],
+            Class::Bard => vec![Abilities::Dexterity, Abilities::Charisma],
+            Class::Cleric | Class::Paladin | Class::Warlock => {
+                vec![Abilities::Wisdom, Abilities::Charisma]
             }
-            Class::Druid => {
-                vec![WeaponType::Simple]
+            Class::Druid | Class::Wizard => vec![Abilities::Intelligence, Abilities::Wisdom],
+            Class::Monk | Class::Ranger => vec![Abilities::Strength, Abilities::Dexterity],
+            Class::Rogue => vec![Abilities::Dexterity, Abilities::Intelligence],
+            Class::Sorcerer => vec![Abilities::Constitution, Abilities::Charisma],
+        }
+    }
+
+    pub fn get_weapon_proficieny(&self) -> Vec<WeaponProficiency> {
+        let simple = WeaponProficiency::Category(WeaponType::Simple);
+        let martial = WeaponProficiency::Category(WeaponType::Martial);
+        match self {
+            Class::Barbarian | Class::Fighter | Class::Paladin | Class::Ranger => {
+                vec![simple, martial]
             }
-            Class::Fighter => {
-                vec![WeaponType::Simple, WeaponType::Martial]
+            Class::Bard | Class::Rogue => {
+                let mut proficiencies = vec![simple];
+                proficiencies.extend(WeaponProficiency::weapons(&[
+                    "Hand Crossbow",
+                    "Longsword",
+                    "Rapier",
+                    "Shortsword",
+                ]));
+                proficiencies
             }
+            Class::Cleric | Class::Warlock => vec![simple],
+            Class::Druid => WeaponProficiency::weapons(&[
+                "Club",
+                "Dagger",
+                "Dart",
+                "Javelin",
+                "Mace",
+                "Quarterstaff",
+                "Scimitar",
+                "Sickle",
+                "Sling",
+                "Spear",
+            ]),
             Class::Monk => {
-                vec![WeaponType::Simple]
-            }
-            Class::Paladin => {
-                vec![WeaponType::Simple, WeaponType::Martial]
-            }
-            Class::Ranger => {
-                vec![WeaponType::Simple, WeaponType::Martial]
-            }
-            Class::Rogue => {
-                vec![WeaponType::Simple]
-            }
-            Class::Sorcerer => {
-                vec![WeaponType::Simple]
-            }
-            Class::Warlock => {
-                vec![WeaponType::Simple]
-            }
-            Class::Wizard => {
-                vec![WeaponType::Simple]
+                let mut proficiencies = vec![simple];
+                proficiencies.extend(WeaponProficiency::weapons(&["Shortsword"]));
+                proficiencies
             }
+            Class::Sorcerer | Class::Wizard => WeaponProficiency::weapons(&[
+                "Dagger",
+                "Dart",
+                "Sling",
+                "Quarterstaff",
+                "Light Crossbow",
+            ]),
+        }
+    }
+
+    /// Number of skills a class picks from its allowed list at 1st level
+    pub fn skill_choice_count(&self) -> usize {
+        match self {
+            Class::Bard | Class::Ranger => 3,
+            Class::Rogue => 4,
+            _ => 2,
         }
     }
 
-    pub fn get_class_skills(&self) -> Vec<Skill> {
+    /// Skills a class is allowed to pick its proficiencies from
+    pub fn allowed_skills(&self) -> Vec<Skill> {
         match self {
-            //In most cases user is free to choose skills (a couple or more)
-            //here for simplicity we are hard coding the values
-            Class::Barbarian => vec![Skill::Athletics, Skill::Survival],
-            Class::Bard => vec![Skill::Acrobatics, Skill::Performance, Skill::History],
-            Class::Cleric => vec![Skill::Medicine, Skill::Religion],
-            Class::Druid => vec![Skill::AnimalHandling, Skill::Nature],
-            Class::Fighter => vec![Skill::Athletics, Skill::Intimidation],
-            Class::Monk => vec![Skill::Religion, Skill::History],
-            Class::Paladin => vec![Skill::Intimidation, Skill::Religion],
-            Class::Ranger => vec![Skill::Survival, Skill::Perception],
-            Class::Rogue => vec![Skill::Stealth, Skill::Deception],
-            Class::Sorcerer => vec![Skill::Persuasion, Skill::Intimidation],
-            Class::Warlock => vec![Skill::Nature, Skill::Arcana],
-            Class::Wizard => vec![Skill::Arcana, Skill::Religion],
+            Class::Barbarian => vec![
+                Skill::AnimalHandling,
+                Skill::Athletics,
+                Skill::Intimidation,
+                Skill::Nature,
+                Skill::Perception,
+                Skill::Survival,
+            ],
+            // Bards choose any three
+            Class::Bard => Skill::iter().collect(),
+            Class::Cleric => vec![
+                Skill::History,
+                Skill::Insight,
+                Skill::Medicine,
+                Skill::Persuasion,
+                Skill::Religion,
+            ],
+            Class::Druid => vec![
+                Skill::Arcana,
+                Skill::AnimalHandling,
+                Skill::Insight,
+                Skill::Medicine,
+                Skill::Nature,
+                Skill::Perception,
+                Skill::Religion,
+                Skill::Survival,
+            ],
+            Class::Fighter => vec![
+                Skill::Acrobatics,
+                Skill::AnimalHandling,
+                Skill::Athletics,
+                Skill::History,
+                Skill::Insight,
+                Skill::Intimidation,
+                Skill::Perception,
+                Skill::Survival,
+            ],
+            Class::Monk => vec![
+                Skill::Acrobatics,
+                Skill::Athletics,
+                Skill::History,
+                Skill::Insight,
+                Skill::Religion,
+                Skill::Stealth,
+            ],
+            Class::Paladin => vec![
+                Skill::Athletics,
+                Skill::Insight,
+                Skill::Intimidation,
+                Skill::Medicine,
+                Skill::Persuasion,
+                Skill::Religion,
+            ],
+            Class::Ranger => vec![
+                Skill::AnimalHandling,
+                Skill::Athletics,
+                Skill::Insight,
+                Skill::Investigation,
+                Skill::Nature,
+                Skill::Perception,
+                Skill::Stealth,
+                Skill::Survival,
+            ],
+            Class::Rogue => vec![
+                Skill::Acrobatics,
+                Skill::Athletics,
+                Skill::Deception,
+                Skill::Insight,
+                Skill::Intimidation,
+                Skill::Investigation,
+                Skill::Perception,
+                Skill::Performance,
+                Skill::Persuasion,
+                Skill::SleightOfHand,
+                Skill::Stealth,
+            ],
+            Class::Sorcerer => vec![
+                Skill::Arcana,
+                Skill::Deception,
+                Skill::Insight,
+                Skill::Intimidation,
+                Skill::Persuasion,
+                Skill::Religion,
+            ],
+            Class::Warlock => vec![
+                Skill::Arcana,
+                Skill::Deception,
+                Skill::History,
+                Skill::Intimidation,
+                Skill::Investigation,
+                Skill::Nature,
+                Skill::Religion,
+            ],
+            Class::Wizard => vec![
+                Skill::Arcana,
+                Skill::History,
+                Skill::Insight,
+                Skill::Investigation,
+                Skill::Medicine,
+                Skill::Religion,
+            ],
         }
     }
 
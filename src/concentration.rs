@@ -0,0 +1,132 @@
+use crate::{ability::Abilities, character::Character, dice::Dice};
+use std::fmt::{self, Formatter};
+
+/// Outcome of the CON save a concentrating character makes when damaged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcentrationCheck {
+    pub spell: String,
+    pub dc: i8,
+    pub roll: u8,
+    pub total: i8,
+    /// The spell that ended on a failed save
+    pub ended: Option<String>,
+}
+
+impl ConcentrationCheck {
+    pub fn maintained(&self) -> bool {
+        self.ended.is_none()
+    }
+}
+
+impl fmt::Display for ConcentrationCheck {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Concentration on {}: {} ({} rolled) vs DC {}",
+            self.spell, self.total, self.roll, self.dc
+        )?;
+        if self.maintained() {
+            write!(f, ", maintained")
+        } else {
+            write!(f, ", {} ends", self.spell)
+        }
+    }
+}
+
+/// DC of the concentration save, half the damage taken but at least 10
+pub fn concentration_dc(damage: u16) -> i8 {
+    (damage / 2).clamp(10, i8::MAX as u16) as i8
+}
+
+impl Character {
+    /// Starts concentrating on `spell`, returning the spell that ended
+    pub fn start_concentration(&mut self, spell: &str) -> Option<String> {
+        self.concentration.replace(spell.to_string())
+    }
+
+    pub fn end_concentration(&mut self) -> Option<String> {
+        self.concentration.take()
+    }
+
+    /// Rolls the CON save after taking `damage`. `None` when the character
+    /// isn't concentrating on anything.
+    pub fn check_concentration(&mut self, damage: u16) -> Option<ConcentrationCheck> {
+        let spell = self.concentration.clone()?;
+        let dc = concentration_dc(damage);
+        let roll = Dice::new(1, 20, 0).roll();
+        let total = roll as i8 + self.saving_throw_bonus(Abilities::Constitution);
+        let ended = if total < dc {
+            self.end_concentration()
+        } else {
+            None
+        };
+
+        Some(ConcentrationCheck {
+            spell,
+            dc,
+            roll,
+            total,
+            ended,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::Class,
+        equipment::DamageType,
+        spell_list::SpellList,
+        test_support::{character_of, set_levels},
+    };
+
+    fn concentrating_cleric(constitution: u8) -> Character {
+        let mut cleric = character_of(Class::Cleric);
+        set_levels(&mut cleric, &[(Class::Cleric, 3)]);
+        cleric.classes[0].spells = SpellList {
+            prepared: vec!["Bless".to_string(), "Hold Person".to_string()],
+            ..SpellList::default()
+        };
+        cleric.stats.set(Abilities::Constitution, constitution);
+        cleric.cast_spell("Bless", 1).unwrap();
+        cleric
+    }
+
+    #[test]
+    fn dc_is_half_the_damage_but_at_least_10() {
+        assert_eq!(concentration_dc(5), 10);
+        assert_eq!(concentration_dc(21), 10);
+        assert_eq!(concentration_dc(22), 11);
+        assert_eq!(concentration_dc(300), 127);
+    }
+
+    #[test]
+    fn second_concentration_spell_ends_the_first() {
+        let mut cleric = concentrating_cleric(10);
+        assert_eq!(cleric.concentration.as_deref(), Some("Bless"));
+
+        let result = cleric.cast_spell("Hold Person", 2).unwrap();
+        assert_eq!(result.ended_concentration.as_deref(), Some("Bless"));
+        assert_eq!(cleric.concentration.as_deref(), Some("Hold Person"));
+    }
+
+    #[test]
+    fn damage_triggers_a_constitution_save() {
+        // CON 30 passes DC 10 on any roll
+        let mut cleric = concentrating_cleric(30);
+        let check = cleric
+            .take_damage(1, DamageType::Slashing)
+            .concentration
+            .unwrap();
+        assert_eq!(check.dc, 10);
+        assert!(check.maintained());
+        assert_eq!(cleric.concentration.as_deref(), Some("Bless"));
+
+        // No roll makes the DC 127 save
+        let check = cleric.check_concentration(254).unwrap();
+        assert_eq!(check.ended.as_deref(), Some("Bless"));
+        assert_eq!(cleric.concentration, None);
+        assert_eq!(cleric.check_concentration(10), None);
+    }
+}
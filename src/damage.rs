@@ -0,0 +1,230 @@
+use crate::{
+    character::Character, concentration::ConcentrationCheck, death_save::LifeState,
+    equipment::DamageType, race::Race,
+};
+use std::fmt::{self, Formatter};
+
+/// How a hit was resolved against a character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamageResult {
+    pub damage_type: DamageType,
+    /// Damage before resistance, vulnerability and immunity
+    pub rolled: u16,
+    /// Damage after resistance, vulnerability and immunity
+    pub taken: u16,
+    pub absorbed_by_temp_hp: u16,
+    pub hp_lost: u16,
+    /// Damage left over at 0 HP reached the hit point maximum
+    pub instant_death: bool,
+    pub concentration: Option<ConcentrationCheck>,
+    pub life_state: LifeState,
+}
+
+impl fmt::Display for DamageResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} damage", self.taken, self.damage_type)?;
+        if self.taken != self.rolled {
+            write!(f, " ({} rolled)", self.rolled)?;
+        }
+        if self.absorbed_by_temp_hp > 0 {
+            write!(f, ", {} absorbed by temporary HP", self.absorbed_by_temp_hp)?;
+        }
+        write!(f, ", -{} HP", self.hp_lost)?;
+        if self.instant_death {
+            write!(f, ", killed outright")?;
+        }
+        if let Some(check) = &self.concentration {
+            write!(f, ", {}", check)?;
+        }
+        if self.life_state != LifeState::Alive {
+            write!(f, " ({})", self.life_state)?;
+        }
+        Ok(())
+    }
+}
+
+impl Race {
+    /// Innate damage resistances, Dwarven Resilience and Hellish Resistance
+    pub fn damage_resistances(&self) -> Vec<DamageType> {
+        match self {
+            Race::Dwarf => vec![DamageType::Poison],
+            Race::Tiefling => vec![DamageType::Fire],
+            _ => vec![],
+        }
+    }
+}
+
+impl Character {
+    /// Immunity cancels the damage, resistance halves it and vulnerability
+    /// doubles it, in that order
+    pub fn modified_damage(&self, amount: u16, damage_type: DamageType) -> u16 {
+        if self.immunities.contains(&damage_type) {
+            return 0;
+        }
+        let mut amount = amount;
+        if self.resistances.contains(&damage_type) {
+            amount /= 2;
+        }
+        if self.vulnerabilities.contains(&damage_type) {
+            amount = amount.saturating_mul(2);
+        }
+        amount
+    }
+
+    #[allow(dead_code)]
+    /// Applies damage to temporary HP first, then to current HP. Damage
+    /// left over at 0 HP that equals the hit point maximum kills outright,
+    /// any other damage at 0 HP is a failed death save.
+    pub fn take_damage(&mut self, amount: u16, damage_type: DamageType) -> DamageResult {
+        let taken = self.modified_damage(amount, damage_type);
+        let was_down = self.current_hp == 0;
+
+        let absorbed_by_temp_hp = taken.min(self.temp_hp);
+        self.temp_hp -= absorbed_by_temp_hp;
+        let remaining = taken - absorbed_by_temp_hp;
+
+        let hp_lost = remaining.min(self.current_hp);
+        self.current_hp -= hp_lost;
+        let instant_death = self.current_hp == 0 && remaining - hp_lost >= self.max_hp;
+
+        if instant_death {
+            self.die_instantly();
+        } else if was_down && remaining > 0 {
+            self.fail_death_saves(1);
+        } else if self.current_hp == 0 && hp_lost > 0 {
+            self.fall_unconscious();
+        }
+
+        let concentration = if taken > 0 && self.current_hp > 0 {
+            self.check_concentration(taken)
+        } else {
+            None
+        };
+
+        DamageResult {
+            damage_type,
+            rolled: amount,
+            taken,
+            absorbed_by_temp_hp,
+            hp_lost,
+            instant_death,
+            concentration,
+            life_state: self.life_state,
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Restores HP up to the maximum, returning how much was regained.
+    /// Any healing wakes a dying or stable character, the dead stay dead.
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        if self.life_state == LifeState::Dead {
+            return 0;
+        }
+        let healed = amount.min(self.max_hp.saturating_sub(self.current_hp));
+        self.current_hp += healed;
+        if self.current_hp > 0 {
+            self.regain_consciousness();
+        }
+        healed
+    }
+
+    #[allow(dead_code)]
+    /// Temporary HP don't stack, the higher value is kept
+    pub fn gain_temp_hp(&mut self, amount: u16) -> u16 {
+        self.temp_hp = self.temp_hp.max(amount);
+        self.temp_hp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{class::Class, death_save::DeathSaves, test_support::character_of};
+
+    fn fighter(hp: u16) -> Character {
+        let mut character = character_of(Class::Fighter);
+        character.max_hp = hp;
+        character.current_hp = hp;
+        character
+    }
+
+    #[test]
+    fn immunity_resistance_and_vulnerability() {
+        let mut character = fighter(20);
+        character.resistances.push(DamageType::Fire);
+        character.vulnerabilities.push(DamageType::Fire);
+        character.immunities.push(DamageType::Poison);
+        assert_eq!(character.modified_damage(7, DamageType::Fire), 6);
+        assert_eq!(character.modified_damage(7, DamageType::Poison), 0);
+        assert_eq!(character.modified_damage(7, DamageType::Cold), 7);
+    }
+
+    #[test]
+    fn temporary_hp_absorb_damage_first() {
+        let mut character = fighter(20);
+        assert_eq!(character.gain_temp_hp(5), 5);
+        assert_eq!(character.gain_temp_hp(3), 5);
+        let result = character.take_damage(8, DamageType::Slashing);
+        assert_eq!(result.absorbed_by_temp_hp, 5);
+        assert_eq!(result.hp_lost, 3);
+        assert_eq!(character.temp_hp, 0);
+        assert_eq!(character.current_hp, 17);
+    }
+
+    #[test]
+    fn dropping_to_zero_starts_dying() {
+        let mut character = fighter(20);
+        let result = character.take_damage(25, DamageType::Bludgeoning);
+        assert_eq!(result.hp_lost, 20);
+        assert!(!result.instant_death);
+        assert_eq!(character.life_state, LifeState::Dying);
+    }
+
+    #[test]
+    fn massive_damage_kills_outright() {
+        let mut character = fighter(20);
+        let result = character.take_damage(40, DamageType::Bludgeoning);
+        assert!(result.instant_death);
+        assert_eq!(character.life_state, LifeState::Dead);
+    }
+
+    #[test]
+    fn massive_damage_ends_concentration_and_death_saves() {
+        let mut character = fighter(20);
+        character.start_concentration("Bless");
+        let result = character.take_damage(40, DamageType::Fire);
+        assert!(result.instant_death);
+        assert_eq!(result.concentration, None);
+        assert_eq!(character.life_state, LifeState::Dead);
+        assert_eq!(character.concentration, None);
+
+        let mut character = fighter(20);
+        character.take_damage(20, DamageType::Fire);
+        character.fail_death_saves(1);
+        character.take_damage(20, DamageType::Fire);
+        assert_eq!(character.life_state, LifeState::Dead);
+        assert_eq!(character.death_saves, DeathSaves::default());
+    }
+
+    #[test]
+    fn damage_while_down_fails_a_death_save() {
+        let mut character = fighter(20);
+        character.take_damage(20, DamageType::Piercing);
+        character.take_damage(1, DamageType::Piercing);
+        assert_eq!(character.death_saves.failures, 1);
+        assert_eq!(character.life_state, LifeState::Dying);
+    }
+
+    #[test]
+    fn healing_wakes_the_dying_but_not_the_dead() {
+        let mut character = fighter(20);
+        character.take_damage(20, DamageType::Piercing);
+        assert_eq!(character.heal(30), 20);
+        assert_eq!(character.life_state, LifeState::Alive);
+
+        character.take_damage(40, DamageType::Piercing);
+        assert_eq!(character.heal(10), 0);
+        assert_eq!(character.current_hp, 0);
+        assert_eq!(character.life_state, LifeState::Dead);
+    }
+}
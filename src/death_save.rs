@@ -0,0 +1,264 @@
+use crate::{character::Character, dice::Dice};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LifeState {
+    #[default]
+    Alive,
+    /// At 0 HP, unconscious and rolling death saves
+    Dying,
+    /// At 0 HP and unconscious, but no longer rolling
+    Stable,
+    Dead,
+}
+
+/// Death saving throws since the character last dropped to 0 HP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DeathSaves {
+    pub successes: u8,
+    pub failures: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeathSave {
+    pub roll: u8,
+    pub saves: DeathSaves,
+    pub state: LifeState,
+}
+
+/// A DC 10 Wisdom (Medicine) check to stabilize a dying creature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StabilizeCheck {
+    pub roll: u8,
+    pub total: i8,
+    pub stabilized: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathSaveError {
+    NotDying(LifeState),
+}
+
+impl fmt::Display for LifeState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LifeState::Alive => write!(f, "Alive"),
+            LifeState::Dying => write!(f, "Dying"),
+            LifeState::Stable => write!(f, "Stable"),
+            LifeState::Dead => write!(f, "Dead"),
+        }
+    }
+}
+
+impl fmt::Display for DeathSaves {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} success(es), {} failure(s)",
+            self.successes, self.failures
+        )
+    }
+}
+
+impl fmt::Display for DeathSave {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Death save {}: {} ({})",
+            self.roll, self.saves, self.state
+        )
+    }
+}
+
+impl fmt::Display for StabilizeCheck {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Medicine {} ({} rolled) vs DC 10", self.total, self.roll)?;
+        if self.stabilized {
+            write!(f, ", stabilized")
+        } else {
+            write!(f, ", still dying")
+        }
+    }
+}
+
+impl fmt::Display for DeathSaveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DeathSaveError::NotDying(state) => write!(f, "Character is {}, not dying", state),
+        }
+    }
+}
+
+impl Character {
+    /// Drops to 0 HP: unconscious, concentration lost, saves start over
+    pub fn fall_unconscious(&mut self) {
+        self.life_state = LifeState::Dying;
+        self.death_saves = DeathSaves::default();
+        self.end_concentration();
+    }
+
+    /// Killed outright by massive damage, concentration ends and any death
+    /// saves rolled so far no longer matter
+    pub fn die_instantly(&mut self) {
+        self.life_state = LifeState::Dead;
+        self.death_saves = DeathSaves::default();
+        self.end_concentration();
+    }
+
+    /// Adds failed death saves, three failures kill. A stable character
+    /// starts rolling again from zero.
+    pub fn fail_death_saves(&mut self, failures: u8) {
+        match self.life_state {
+            LifeState::Dead => return,
+            LifeState::Stable => self.death_saves = DeathSaves::default(),
+            _ => {}
+        }
+        self.life_state = LifeState::Dying;
+        self.death_saves.failures = (self.death_saves.failures + failures).min(3);
+        if self.death_saves.failures >= 3 {
+            self.life_state = LifeState::Dead;
+        }
+    }
+
+    /// Back on your feet after regaining HP
+    pub fn regain_consciousness(&mut self) {
+        self.life_state = LifeState::Alive;
+        self.death_saves = DeathSaves::default();
+    }
+
+    #[allow(dead_code)]
+    /// Rolls a d20, 10 or higher succeeds. Three successes stabilize,
+    /// a natural 20 regains 1 HP and a natural 1 counts as two failures.
+    pub fn roll_death_save(&mut self) -> Result<DeathSave, DeathSaveError> {
+        if self.life_state != LifeState::Dying {
+            return Err(DeathSaveError::NotDying(self.life_state));
+        }
+
+        let roll = Dice::new(1, 20, 0).roll();
+        match roll {
+            20 => {
+                self.current_hp = 1;
+                self.regain_consciousness();
+            }
+            1 => self.fail_death_saves(2),
+            2..=9 => self.fail_death_saves(1),
+            _ => {
+                self.death_saves.successes += 1;
+                if self.death_saves.successes >= 3 {
+                    self.life_state = LifeState::Stable;
+                    self.death_saves = DeathSaves::default();
+                }
+            }
+        }
+
+        Ok(DeathSave {
+            roll,
+            saves: self.death_saves,
+            state: self.life_state,
+        })
+    }
+
+    #[allow(dead_code)]
+    /// Someone else's Medicine check to stabilize the character,
+    /// `medicine_bonus` being the healer's `skill_bonus(Skill::Medicine)`
+    pub fn stabilize(&mut self, medicine_bonus: i8) -> Result<StabilizeCheck, DeathSaveError> {
+        if self.life_state != LifeState::Dying {
+            return Err(DeathSaveError::NotDying(self.life_state));
+        }
+
+        let roll = Dice::new(1, 20, 0).roll();
+        let total = roll as i8 + medicine_bonus;
+        let stabilized = total >= 10;
+        if stabilized {
+            self.life_state = LifeState::Stable;
+            self.death_saves = DeathSaves::default();
+        }
+        Ok(StabilizeCheck {
+            roll,
+            total,
+            stabilized,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{class::Class, equipment::DamageType, test_support::character_of};
+
+    fn dying() -> Character {
+        let mut character = character_of(Class::Fighter);
+        character.max_hp = 20;
+        character.current_hp = 20;
+        character.take_damage(20, DamageType::Slashing);
+        character
+    }
+
+    #[test]
+    fn three_failures_kill() {
+        let mut character = dying();
+        character.fail_death_saves(2);
+        assert_eq!(character.life_state, LifeState::Dying);
+        character.fail_death_saves(2);
+        assert_eq!(character.death_saves.failures, 3);
+        assert_eq!(character.life_state, LifeState::Dead);
+    }
+
+    #[test]
+    fn only_the_dying_roll_or_get_stabilized() {
+        let mut character = dying();
+        character.regain_consciousness();
+        assert_eq!(
+            character.roll_death_save(),
+            Err(DeathSaveError::NotDying(LifeState::Alive))
+        );
+        assert_eq!(
+            character.stabilize(20),
+            Err(DeathSaveError::NotDying(LifeState::Alive))
+        );
+    }
+
+    #[test]
+    fn death_saves_end_stable_alive_or_dead() {
+        for _ in 0..50 {
+            let mut character = dying();
+            while character.life_state == LifeState::Dying {
+                character.roll_death_save().unwrap();
+            }
+            match character.life_state {
+                LifeState::Alive => assert_eq!(character.current_hp, 1),
+                LifeState::Stable => assert_eq!(character.death_saves, DeathSaves::default()),
+                LifeState::Dead => assert_eq!(character.death_saves.failures, 3),
+                LifeState::Dying => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn damage_while_stable_starts_the_saves_over() {
+        let mut character = dying();
+        character.death_saves.failures = 2;
+        assert!(character.stabilize(20).unwrap().stabilized);
+        assert_eq!(character.life_state, LifeState::Stable);
+
+        character.take_damage(1, DamageType::Slashing);
+        assert_eq!(character.life_state, LifeState::Dying);
+        assert_eq!(
+            character.death_saves,
+            DeathSaves {
+                successes: 0,
+                failures: 1
+            }
+        );
+    }
+
+    #[test]
+    fn healing_a_stable_character_wakes_them() {
+        let mut character = dying();
+        character.stabilize(20).unwrap();
+        character.heal(1);
+        assert_eq!(character.life_state, LifeState::Alive);
+        assert_eq!(character.death_saves, DeathSaves::default());
+    }
+}
@@ -1,7 +1,8 @@
 use rand::{Rng, thread_rng};
 use regex::Regex;
+use std::fmt::{self, Formatter};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Dice {
     pub dice_count: u8,
     pub dice_sides: u8,
@@ -25,20 +26,67 @@ impl Dice {
             total += rnd.gen_range(1..=self.dice_sides) as i8;
         }
 
-        total += self.modifier as i8;
+        total += self.modifier;
 
         total.max(1) as u8
     }
 
+    /// Rolls without the u8 cap, for big pools like an upcast critical Fireball
+    pub fn roll_total(&self) -> i16 {
+        let mut rnd = thread_rng();
+        let rolled: i16 = (0..self.dice_count)
+            .map(|_| rnd.gen_range(1..=self.dice_sides) as i16)
+            .sum();
+        rolled + self.modifier as i16
+    }
+
+    /// Like `roll_total`, but a die showing `reroll_at_or_below` or less is
+    /// rolled once more and the new roll is kept, as Great Weapon Fighting does
+    pub fn roll_total_rerolling(&self, reroll_at_or_below: u8) -> i16 {
+        let mut rnd = thread_rng();
+        let rolled: i16 = (0..self.dice_count)
+            .map(|_| {
+                let roll = rnd.gen_range(1..=self.dice_sides);
+                if roll <= reroll_at_or_below {
+                    rnd.gen_range(1..=self.dice_sides) as i16
+                } else {
+                    roll as i16
+                }
+            })
+            .sum();
+        rolled + self.modifier as i16
+    }
+
     pub fn roll_for_ability() -> u8 {
         let mut rolls: [u8; 4] = [0; 4];
-        for i in 0..4 {
-            rolls[i] = Dice::new(1, 6, 0).roll();
+        for roll in rolls.iter_mut() {
+            *roll = Dice::new(1, 6, 0).roll();
         }
         rolls.sort();
         rolls[1..].iter().sum()
     }
 
+    /// Parses "NdS" or "NdS±M" notation such as weapon or spell damage.
+    /// Zero dice or zero-sided dice can't be rolled and are rejected.
+    pub fn from_notation(notation: &str) -> Option<Dice> {
+        let notation = notation.trim().to_lowercase();
+        let (count, rest) = notation.split_once('d')?;
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse().ok()?
+        };
+        let (sides, modifier) = match rest.find(['+', '-']) {
+            Some(index) => (&rest[..index], rest[index..].parse().ok()?),
+            None => (rest, 0),
+        };
+        let sides = sides.parse().ok()?;
+        if count == 0 || sides == 0 {
+            return None;
+        }
+        Some(Dice::new(count, sides, modifier))
+    }
+
     #[allow(dead_code)]
     pub fn parse_dice_roll(dice_roll: String) -> u8 {
         let dice_roll = dice_roll.to_lowercase();
@@ -102,3 +150,40 @@ impl Default for Dice {
         Dice::new(1, 6, 0)
     }
 }
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.dice_count, self.dice_sides)?;
+        if self.modifier != 0 {
+            write!(f, "{:+}", self.modifier)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dice_notation() {
+        assert_eq!(Dice::from_notation("2d6"), Some(Dice::new(2, 6, 0)));
+        assert_eq!(Dice::from_notation("d8+3"), Some(Dice::new(1, 8, 3)));
+        assert_eq!(Dice::from_notation(" 1D10-1 "), Some(Dice::new(1, 10, -1)));
+    }
+
+    #[test]
+    fn rejects_dice_that_cant_be_rolled() {
+        for notation in ["0d6", "1d0", "d0", "2d", "d", "12", "xd6"] {
+            assert_eq!(Dice::from_notation(notation), None, "{}", notation);
+        }
+    }
+
+    #[test]
+    fn rerolls_stay_within_the_dice() {
+        let dice = Dice::new(2, 6, 1);
+        for _ in 0..100 {
+            assert!((3..=13).contains(&dice.roll_total_rerolling(2)));
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::fmt::{self, Formatter};
 
+use crate::dice::Dice;
+
 use serde::{Deserialize, Serialize};
 use strum_macros::{self, Display};
 
 use std::collections::HashMap;
+use std::mem;
 use std::{fs, io};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,21 +18,67 @@ pub struct Weapon {
     pub damage_type: DamageType,
     pub weight: f32,
     pub price: f32,
-    pub properties: Vec<String>,
+    pub properties: Vec<WeaponProperty>,
+}
+
+/// Weapon properties from the PHB, ranges are in feet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeaponProperty {
+    Ammunition {
+        normal: u16,
+        long: u16,
+    },
+    Finesse,
+    Heavy,
+    Light,
+    Loading,
+    Reach,
+    Thrown {
+        normal: u16,
+        long: u16,
+    },
+    TwoHanded,
+    /// Damage dice when wielded with two hands
+    Versatile(String),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WeaponType {
     Simple,
     Martial,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+/// Proficiency with a whole weapon category or with one named weapon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeaponProficiency {
+    Category(WeaponType),
+    Weapon(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
 pub enum WeaponRange {
     Melee,
     Ranged,
 }
 
+impl fmt::Display for WeaponProperty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WeaponProperty::Ammunition { normal, long } => {
+                write!(f, "Ammunition ({}/{})", normal, long)
+            }
+            WeaponProperty::Finesse => write!(f, "Finesse"),
+            WeaponProperty::Heavy => write!(f, "Heavy"),
+            WeaponProperty::Light => write!(f, "Light"),
+            WeaponProperty::Loading => write!(f, "Loading"),
+            WeaponProperty::Reach => write!(f, "Reach"),
+            WeaponProperty::Thrown { normal, long } => write!(f, "Thrown ({}/{})", normal, long),
+            WeaponProperty::TwoHanded => write!(f, "Two-Handed"),
+            WeaponProperty::Versatile(dice) => write!(f, "Versatile ({})", dice),
+        }
+    }
+}
+
 impl fmt::Display for WeaponType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -39,11 +88,21 @@ impl fmt::Display for WeaponType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DamageType {
     Piercing,
     Slashing,
     Bludgeoning,
+    Acid,
+    Cold,
+    Fire,
+    Force,
+    Lightning,
+    Necrotic,
+    Poison,
+    Psychic,
+    Radiant,
+    Thunder,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,6 +116,16 @@ impl fmt::Display for DamageType {
             DamageType::Bludgeoning => "Bludgeoning",
             DamageType::Piercing => "Piercing",
             DamageType::Slashing => "Slashing",
+            DamageType::Acid => "Acid",
+            DamageType::Cold => "Cold",
+            DamageType::Fire => "Fire",
+            DamageType::Force => "Force",
+            DamageType::Lightning => "Lightning",
+            DamageType::Necrotic => "Necrotic",
+            DamageType::Poison => "Poison",
+            DamageType::Psychic => "Psychic",
+            DamageType::Radiant => "Radiant",
+            DamageType::Thunder => "Thunder",
         };
         write!(f, "{}", damage_type)
     }
@@ -71,13 +140,17 @@ pub struct Armor {
     pub weight: f32,
     pub price: f32,
     pub stealth_disadvantage: bool,
+    /// +1 to +3 for magic armor
+    #[serde(default)]
+    pub magic_bonus: i8,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ArmorType {
     Light,
     Medium,
     Heavy,
+    Shield,
 }
 
 impl fmt::Display for ArmorType {
@@ -86,6 +159,7 @@ impl fmt::Display for ArmorType {
             ArmorType::Light => write!(f, "Light"),
             ArmorType::Medium => write!(f, "Medium"),
             ArmorType::Heavy => write!(f, "Heavy"),
+            ArmorType::Shield => write!(f, "Shield"),
         }
     }
 }
@@ -95,13 +169,33 @@ struct ArmorDatabase {
     armor: Vec<Armor>,
 }
 
+/// Adventuring gear such as packs, ammunition and focuses. `quantity` is
+/// the bundle size the price and weight apply to, e.g. 20 arrows.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Gear {
+    pub name: String,
+    pub quantity: u16,
+    pub weight: f32,
+    pub price: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GearDatabase {
+    gear: Vec<Gear>,
+}
+
 ///IMPL Section
 impl Armor {
     pub fn load_armor_database() -> Result<HashMap<String, Armor>, io::Error> {
-        let toml_content = fs::read_to_string("data/armor.toml")?;
+        let toml_content = fs::read_to_string("data/armor.toml")
+            .map_err(|e| io::Error::new(e.kind(), format!("data/armor.toml: {}", e)))?;
 
-        let database: ArmorDatabase = toml::from_str(&toml_content)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let database: ArmorDatabase = toml::from_str(&toml_content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("data/armor.toml: {}", e),
+            )
+        })?;
 
         let armor_map: HashMap<String, Armor> = database
             .armor
@@ -112,35 +206,278 @@ impl Armor {
         Ok(armor_map)
     }
 
+    /// Shields stack with armor and with a Barbarian's Unarmored Defense
+    pub fn is_shield(&self) -> bool {
+        self.armor_type == ArmorType::Shield
+    }
+
+    #[allow(dead_code)]
     pub fn get_armor(key: &str) -> Option<Armor> {
         Armor::load_armor_database().ok()?.get(key).cloned()
     }
 }
 
-impl Weapon {
-    pub fn load_weapon_database() -> Result<HashMap<String, Weapon>, io::Error> {
-        let toml_content = fs::read_to_string("data/weapon.toml")?;
+impl Gear {
+    pub fn load_gear_database() -> Result<HashMap<String, Gear>, io::Error> {
+        let toml_content = fs::read_to_string("data/gear.toml")
+            .map_err(|e| io::Error::new(e.kind(), format!("data/gear.toml: {}", e)))?;
 
-        let database: WeaponDatabase = toml::from_str(&toml_content)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let database: GearDatabase = toml::from_str(&toml_content).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("data/gear.toml: {}", e))
+        })?;
 
-        let weapon_map: HashMap<String, Weapon> = database
-            .weapon
+        let gear_map: HashMap<String, Gear> = database
+            .gear
             .into_iter()
-            .map(|weapon| (weapon.name.clone(), weapon))
+            .map(|gear| (gear.name.clone(), gear))
             .collect();
 
+        Ok(gear_map)
+    }
+}
+
+impl Weapon {
+    pub fn load_weapon_database() -> Result<HashMap<String, Weapon>, io::Error> {
+        let toml_content = fs::read_to_string("data/weapon.toml")
+            .map_err(|e| io::Error::new(e.kind(), format!("data/weapon.toml: {}", e)))?;
+
+        let database: WeaponDatabase = toml::from_str(&toml_content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("data/weapon.toml: {}", e),
+            )
+        })?;
+
+        let mut weapon_map: HashMap<String, Weapon> = HashMap::new();
+        for weapon in database.weapon {
+            weapon
+                .validate()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            weapon_map.insert(weapon.name.clone(), weapon);
+        }
+
         Ok(weapon_map)
     }
 
+    /// Checks what the TOML types can't express
+    fn validate(&self) -> Result<(), String> {
+        let fail = |reason: String| {
+            Err(format!(
+                "data/weapon.toml: weapon '{}' {}",
+                self.name, reason
+            ))
+        };
+
+        let flat_damage = self.damage.trim().parse::<u8>().ok();
+        if Dice::from_notation(&self.damage).is_none() && flat_damage.is_none_or(|flat| flat == 0) {
+            return fail(format!(
+                "has damage '{}', expected dice like 1d8 or a flat value above 0",
+                self.damage
+            ));
+        }
+        for property in &self.properties {
+            match property {
+                WeaponProperty::Ammunition { normal, long }
+                | WeaponProperty::Thrown { normal, long }
+                    if normal > long =>
+                {
+                    return fail(format!(
+                        "has a normal range above its long range {}",
+                        property
+                    ));
+                }
+                WeaponProperty::Versatile(dice) if Dice::from_notation(dice).is_none() => {
+                    return fail(format!(
+                        "has versatile damage '{}', expected dice like 1d10",
+                        dice
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if self.weapon_range == WeaponRange::Ranged && self.range().is_none() {
+            return fail("is ranged but has no Ammunition or Thrown range".to_string());
+        }
+        if self.is_two_handed() && self.is_versatile() {
+            return fail("is both Two-Handed and Versatile".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Properties without range or dice, `Ammunition` and `Thrown` match any range
+    pub fn has_property(&self, property: WeaponProperty) -> bool {
+        self.properties
+            .iter()
+            .any(|known| mem::discriminant(known) == mem::discriminant(&property))
+    }
+
+    pub fn is_two_handed(&self) -> bool {
+        self.has_property(WeaponProperty::TwoHanded)
+    }
+
+    pub fn is_versatile(&self) -> bool {
+        self.properties
+            .iter()
+            .any(|property| matches!(property, WeaponProperty::Versatile(_)))
+    }
+
+    /// Normal and long range of ammunition or a thrown weapon
+    pub fn range(&self) -> Option<(u16, u16)> {
+        self.properties.iter().find_map(|property| match property {
+            WeaponProperty::Ammunition { normal, long }
+            | WeaponProperty::Thrown { normal, long } => Some((*normal, *long)),
+            _ => None,
+        })
+    }
+
+    /// Melee reach in feet
+    pub fn reach(&self) -> u16 {
+        if self.has_property(WeaponProperty::Reach) {
+            10
+        } else {
+            5
+        }
+    }
+
+    /// Damage dice, a flat damage value like the blowgun's 1 is rolled as
+    /// that many d1 so it always deals exactly that much
+    pub fn damage_dice(&self) -> Dice {
+        Dice::from_notation(&self.damage).unwrap_or_else(|| {
+            let flat = self.damage.trim().parse().unwrap_or(1);
+            Dice::new(flat.max(1), 1, 0)
+        })
+    }
+
+    /// Damage dice with two hands, the weapon's own dice unless it is versatile
+    pub fn versatile_damage_dice(&self) -> Dice {
+        self.properties
+            .iter()
+            .find_map(|property| match property {
+                WeaponProperty::Versatile(dice) => Dice::from_notation(dice),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.damage_dice())
+    }
+
+    #[allow(dead_code)]
     pub fn get_weapon(key: &str) -> Option<Weapon> {
         Weapon::load_weapon_database().ok()?.get(key).cloned()
     }
 }
 
+impl WeaponProficiency {
+    pub fn weapons(names: &[&str]) -> Vec<WeaponProficiency> {
+        names
+            .iter()
+            .map(|name| WeaponProficiency::Weapon(name.to_string()))
+            .collect()
+    }
+
+    pub fn covers(&self, weapon: &Weapon) -> bool {
+        match self {
+            WeaponProficiency::Category(weapon_type) => *weapon_type == weapon.weapon_type,
+            WeaponProficiency::Weapon(name) => name.eq_ignore_ascii_case(&weapon.name),
+        }
+    }
+}
+
+impl fmt::Display for WeaponProficiency {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WeaponProficiency::Category(weapon_type) => write!(f, "{} weapons", weapon_type),
+            WeaponProficiency::Weapon(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 impl fmt::Display for Weapon {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         writeln!(f, "────────────────────")?;
         write!(f, "Weapon: {}", self.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        background::Background,
+        character::Character,
+        class::Class,
+        race::Race,
+        test_support::{character, character_of},
+    };
+
+    fn proficient(character: &Character, name: &str) -> bool {
+        character.is_proficient_with(&Weapon::get_weapon(name).unwrap())
+    }
+
+    #[test]
+    fn classes_are_proficient_with_named_weapons() {
+        let rogue = character_of(Class::Rogue);
+        assert!(proficient(&rogue, "Dagger"));
+        assert!(proficient(&rogue, "Rapier"));
+        assert!(proficient(&rogue, "Hand Crossbow"));
+        assert!(!proficient(&rogue, "Greataxe"));
+
+        let wizard = character_of(Class::Wizard);
+        assert!(proficient(&wizard, "Quarterstaff"));
+        assert!(proficient(&wizard, "Light Crossbow"));
+        assert!(!proficient(&wizard, "Mace"));
+
+        let fighter = character_of(Class::Fighter);
+        assert!(proficient(&fighter, "Greataxe"));
+    }
+
+    #[test]
+    fn race_adds_weapon_training() {
+        let elf = character(Race::Elf, Class::Wizard, Background::Sage);
+        assert!(proficient(&elf, "Longsword"));
+        assert!(proficient(&elf, "Longbow"));
+        assert!(!proficient(&elf, "Warhammer"));
+    }
+
+    fn parse(properties: &str) -> Result<Weapon, String> {
+        let toml = format!(
+            "name = \"Test Spear\"\n\
+             weapon_type = \"Simple\"\n\
+             weapon_range = \"Melee\"\n\
+             damage = \"1d6\"\n\
+             damage_type = \"Piercing\"\n\
+             weight = 3.0\n\
+             price = 1.0\n\
+             properties = {}",
+            properties
+        );
+        let weapon: Weapon = toml::from_str(&toml).map_err(|e| e.to_string())?;
+        weapon.validate()?;
+        Ok(weapon)
+    }
+
+    #[test]
+    fn properties_are_typed_at_load_time() {
+        let spear =
+            parse(r#"[{ Thrown = { normal = 20, long = 60 } }, { Versatile = "1d8" }]"#).unwrap();
+        assert_eq!(spear.range(), Some((20, 60)));
+        assert_eq!(spear.versatile_damage_dice().dice_sides, 8);
+
+        assert!(parse(r#"["Two-Handed"]"#).is_err());
+        assert_eq!(
+            parse(r#"[{ Thrown = { normal = 60, long = 20 } }]"#).unwrap_err(),
+            "data/weapon.toml: weapon 'Test Spear' has a normal range above its long range \
+             Thrown (60/20)"
+        );
+        assert!(parse(r#"[{ Versatile = "big" }]"#).is_err());
+    }
+
+    #[test]
+    fn weapon_database_ranges_and_reach() {
+        let weapon = |name: &str| Weapon::get_weapon(name).unwrap();
+        assert_eq!(weapon("Longbow").range(), Some((150, 600)));
+        assert_eq!(weapon("Javelin").range(), Some((30, 120)));
+        assert_eq!(weapon("Longsword").range(), None);
+        assert_eq!(weapon("Longsword").reach(), 5);
+        assert_eq!(parse(r#"["Reach", "Heavy"]"#).unwrap().reach(), 10);
+    }
+}
@@ -0,0 +1,721 @@
+use crate::{ability::Abilities, class::Class, dice::Dice};
+use std::fmt::{self, Formatter};
+
+/// A feature granted by a class or subclass once the character reaches `level`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub level: u8,
+}
+
+impl Feature {
+    pub const fn new(level: u8, name: &'static str, description: &'static str) -> Self {
+        Feature {
+            name,
+            description,
+            level,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (lvl {}): {}",
+            self.name, self.level, self.description
+        )
+    }
+}
+
+const ASI: &str = "Increase one ability score by 2, or two by 1, or take a feat.";
+
+impl Class {
+    /// Class feature progression from level 1 to 20
+    pub fn features(&self) -> Vec<Feature> {
+        let mut features = match self {
+            Class::Barbarian => vec![
+                Feature::new(
+                    1,
+                    "Rage",
+                    "Enter a rage for bonus damage and resistance to physical damage.",
+                ),
+                Feature::new(
+                    1,
+                    "Unarmored Defense",
+                    "Unarmored AC equals 10 + DEX + CON.",
+                ),
+                Feature::new(
+                    2,
+                    "Reckless Attack",
+                    "Gain advantage on STR attacks, attacks against you gain advantage.",
+                ),
+                Feature::new(
+                    2,
+                    "Danger Sense",
+                    "Advantage on DEX saves against effects you can see.",
+                ),
+                Feature::new(3, "Primal Path", "Choose a path that shapes your rage."),
+                Feature::new(
+                    5,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(
+                    5,
+                    "Fast Movement",
+                    "Speed increases by 10 feet without heavy armor.",
+                ),
+                Feature::new(7, "Feral Instinct", "Advantage on initiative rolls."),
+                Feature::new(
+                    9,
+                    "Brutal Critical",
+                    "Roll one additional weapon damage die on a critical hit.",
+                ),
+                Feature::new(
+                    11,
+                    "Relentless Rage",
+                    "Drop to 1 hit point instead of 0 on a successful CON save.",
+                ),
+                Feature::new(
+                    13,
+                    "Brutal Critical",
+                    "Roll two additional weapon damage dice on a critical hit.",
+                ),
+                Feature::new(
+                    15,
+                    "Persistent Rage",
+                    "Rage only ends early if you choose or fall unconscious.",
+                ),
+                Feature::new(
+                    17,
+                    "Brutal Critical",
+                    "Roll three additional weapon damage dice on a critical hit.",
+                ),
+                Feature::new(
+                    18,
+                    "Indomitable Might",
+                    "Use your STR score if a STR check rolls lower.",
+                ),
+                Feature::new(
+                    20,
+                    "Primal Champion",
+                    "STR and CON increase by 4, to a maximum of 24.",
+                ),
+            ],
+            Class::Bard => vec![
+                Feature::new(1, "Spellcasting", "Cast bard spells using CHA."),
+                Feature::new(
+                    1,
+                    "Bardic Inspiration",
+                    "Grant an ally an inspiration die to add to a roll.",
+                ),
+                Feature::new(
+                    2,
+                    "Jack of All Trades",
+                    "Add half proficiency to ability checks without proficiency.",
+                ),
+                Feature::new(
+                    2,
+                    "Song of Rest",
+                    "Allies regain extra hit points during a short rest.",
+                ),
+                Feature::new(
+                    3,
+                    "Bard College",
+                    "Choose a college that shapes your performance.",
+                ),
+                Feature::new(3, "Expertise", "Double proficiency for two chosen skills."),
+                Feature::new(
+                    5,
+                    "Font of Inspiration",
+                    "Bardic Inspiration recovers on a short rest.",
+                ),
+                Feature::new(
+                    6,
+                    "Countercharm",
+                    "Grant advantage on saves against being frightened or charmed.",
+                ),
+                Feature::new(10, "Expertise", "Double proficiency for two more skills."),
+                Feature::new(10, "Magical Secrets", "Learn two spells from any class."),
+                Feature::new(
+                    14,
+                    "Magical Secrets",
+                    "Learn two more spells from any class.",
+                ),
+                Feature::new(
+                    18,
+                    "Magical Secrets",
+                    "Learn two more spells from any class.",
+                ),
+                Feature::new(
+                    20,
+                    "Superior Inspiration",
+                    "Regain one Bardic Inspiration when rolling initiative with none left.",
+                ),
+            ],
+            Class::Cleric => vec![
+                Feature::new(1, "Spellcasting", "Cast cleric spells using WIS."),
+                Feature::new(1, "Divine Domain", "Choose a domain tied to your deity."),
+                Feature::new(
+                    2,
+                    "Channel Divinity",
+                    "Channel divine energy for domain effects.",
+                ),
+                Feature::new(2, "Turn Undead", "Channel Divinity to make undead flee."),
+                Feature::new(
+                    5,
+                    "Destroy Undead",
+                    "Turned undead of low challenge rating are destroyed.",
+                ),
+                Feature::new(
+                    10,
+                    "Divine Intervention",
+                    "Call on your deity to intervene.",
+                ),
+                Feature::new(
+                    20,
+                    "Divine Intervention Improvement",
+                    "Divine Intervention succeeds automatically.",
+                ),
+            ],
+            Class::Druid => vec![
+                Feature::new(1, "Druidic", "Know the secret language of druids."),
+                Feature::new(1, "Spellcasting", "Cast druid spells using WIS."),
+                Feature::new(2, "Wild Shape", "Magically assume the shape of a beast."),
+                Feature::new(2, "Druid Circle", "Choose a circle of druids."),
+                Feature::new(18, "Timeless Body", "Age one year for every ten that pass."),
+                Feature::new(18, "Beast Spells", "Cast spells while in Wild Shape."),
+                Feature::new(
+                    20,
+                    "Archdruid",
+                    "Use Wild Shape an unlimited number of times.",
+                ),
+            ],
+            Class::Fighter => vec![
+                Feature::new(1, "Fighting Style", "Adopt a particular style of fighting."),
+                Feature::new(
+                    1,
+                    "Second Wind",
+                    "Regain 1d10 + fighter level hit points as a bonus action.",
+                ),
+                Feature::new(
+                    2,
+                    "Action Surge",
+                    "Take one additional action on your turn.",
+                ),
+                Feature::new(
+                    3,
+                    "Martial Archetype",
+                    "Choose an archetype that shapes your fighting.",
+                ),
+                Feature::new(
+                    5,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(6, "Ability Score Improvement", ASI),
+                Feature::new(9, "Indomitable", "Reroll a failed saving throw."),
+                Feature::new(
+                    11,
+                    "Extra Attack",
+                    "Attack three times when you take the Attack action.",
+                ),
+                Feature::new(
+                    13,
+                    "Indomitable",
+                    "Use Indomitable twice between long rests.",
+                ),
+                Feature::new(14, "Ability Score Improvement", ASI),
+                Feature::new(17, "Action Surge", "Use Action Surge twice between rests."),
+                Feature::new(
+                    17,
+                    "Indomitable",
+                    "Use Indomitable three times between long rests.",
+                ),
+                Feature::new(
+                    20,
+                    "Extra Attack",
+                    "Attack four times when you take the Attack action.",
+                ),
+            ],
+            Class::Monk => vec![
+                Feature::new(
+                    1,
+                    "Unarmored Defense",
+                    "Unarmored AC equals 10 + DEX + WIS.",
+                ),
+                Feature::new(
+                    1,
+                    "Martial Arts",
+                    "Use DEX and the martial arts die for unarmed strikes and monk weapons.",
+                ),
+                Feature::new(
+                    2,
+                    "Ki",
+                    "Spend ki points on Flurry of Blows, Patient Defense and Step of the Wind.",
+                ),
+                Feature::new(
+                    2,
+                    "Unarmored Movement",
+                    "Speed increases while not wearing armor or a shield.",
+                ),
+                Feature::new(3, "Monastic Tradition", "Choose a monastic tradition."),
+                Feature::new(
+                    3,
+                    "Deflect Missiles",
+                    "Reduce damage from ranged weapon attacks.",
+                ),
+                Feature::new(
+                    4,
+                    "Slow Fall",
+                    "Reduce falling damage by five times your monk level.",
+                ),
+                Feature::new(
+                    5,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(5, "Stunning Strike", "Spend ki to stun a creature you hit."),
+                Feature::new(
+                    6,
+                    "Ki-Empowered Strikes",
+                    "Unarmed strikes count as magical.",
+                ),
+                Feature::new(
+                    7,
+                    "Evasion",
+                    "Take no damage on a successful DEX save for half damage.",
+                ),
+                Feature::new(
+                    7,
+                    "Stillness of Mind",
+                    "End a charm or fear effect on yourself.",
+                ),
+                Feature::new(10, "Purity of Body", "Immune to disease and poison."),
+                Feature::new(
+                    13,
+                    "Tongue of the Sun and Moon",
+                    "Understand and be understood in all spoken languages.",
+                ),
+                Feature::new(14, "Diamond Soul", "Proficiency in all saving throws."),
+                Feature::new(
+                    15,
+                    "Timeless Body",
+                    "No longer suffer the frailty of old age.",
+                ),
+                Feature::new(
+                    18,
+                    "Empty Body",
+                    "Spend ki to become invisible or cast astral projection.",
+                ),
+                Feature::new(
+                    20,
+                    "Perfect Self",
+                    "Regain 4 ki points when rolling initiative with none left.",
+                ),
+            ],
+            Class::Paladin => vec![
+                Feature::new(1, "Divine Sense", "Detect celestials, fiends and undead."),
+                Feature::new(
+                    1,
+                    "Lay on Hands",
+                    "Heal from a pool of five times your paladin level.",
+                ),
+                Feature::new(2, "Fighting Style", "Adopt a particular style of fighting."),
+                Feature::new(2, "Spellcasting", "Cast paladin spells using CHA."),
+                Feature::new(
+                    2,
+                    "Divine Smite",
+                    "Expend a spell slot to deal extra radiant damage.",
+                ),
+                Feature::new(3, "Divine Health", "Immune to disease."),
+                Feature::new(
+                    3,
+                    "Sacred Oath",
+                    "Swear an oath that binds you as a paladin.",
+                ),
+                Feature::new(
+                    5,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(
+                    6,
+                    "Aura of Protection",
+                    "You and nearby allies add CHA modifier to saves.",
+                ),
+                Feature::new(
+                    10,
+                    "Aura of Courage",
+                    "You and nearby allies can't be frightened.",
+                ),
+                Feature::new(
+                    11,
+                    "Improved Divine Smite",
+                    "Melee weapon hits deal an extra 1d8 radiant damage.",
+                ),
+                Feature::new(
+                    14,
+                    "Cleansing Touch",
+                    "End a spell on yourself or a willing creature.",
+                ),
+                Feature::new(18, "Aura Improvements", "Your auras extend to 30 feet."),
+            ],
+            Class::Ranger => vec![
+                Feature::new(
+                    1,
+                    "Favored Enemy",
+                    "Advantage to track and recall lore about a chosen enemy type.",
+                ),
+                Feature::new(
+                    1,
+                    "Natural Explorer",
+                    "Excel at travel in a favored terrain.",
+                ),
+                Feature::new(2, "Fighting Style", "Adopt a particular style of fighting."),
+                Feature::new(2, "Spellcasting", "Cast ranger spells using WIS."),
+                Feature::new(3, "Ranger Archetype", "Choose an archetype to emulate."),
+                Feature::new(
+                    3,
+                    "Primeval Awareness",
+                    "Expend a spell slot to sense nearby creature types.",
+                ),
+                Feature::new(
+                    5,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(6, "Favored Enemy", "Choose an additional favored enemy."),
+                Feature::new(
+                    6,
+                    "Natural Explorer",
+                    "Choose an additional favored terrain.",
+                ),
+                Feature::new(
+                    8,
+                    "Land's Stride",
+                    "Move through nonmagical difficult terrain freely.",
+                ),
+                Feature::new(
+                    10,
+                    "Natural Explorer",
+                    "Choose an additional favored terrain.",
+                ),
+                Feature::new(
+                    10,
+                    "Hide in Plain Sight",
+                    "Camouflage yourself for +10 to Stealth.",
+                ),
+                Feature::new(14, "Favored Enemy", "Choose an additional favored enemy."),
+                Feature::new(
+                    14,
+                    "Vanish",
+                    "Hide as a bonus action and can't be tracked nonmagically.",
+                ),
+                Feature::new(
+                    18,
+                    "Feral Senses",
+                    "Sense invisible creatures within 30 feet.",
+                ),
+                Feature::new(
+                    20,
+                    "Foe Slayer",
+                    "Add WIS modifier to an attack or damage roll against a favored enemy.",
+                ),
+            ],
+            Class::Rogue => vec![
+                Feature::new(
+                    1,
+                    "Expertise",
+                    "Double proficiency for two chosen skills or thieves' tools.",
+                ),
+                Feature::new(
+                    1,
+                    "Sneak Attack",
+                    "Deal extra damage once per turn with advantage or an adjacent ally.",
+                ),
+                Feature::new(
+                    1,
+                    "Thieves' Cant",
+                    "Know the secret mix of dialect and code of thieves.",
+                ),
+                Feature::new(
+                    2,
+                    "Cunning Action",
+                    "Dash, Disengage or Hide as a bonus action.",
+                ),
+                Feature::new(
+                    3,
+                    "Roguish Archetype",
+                    "Choose an archetype that shapes your abilities.",
+                ),
+                Feature::new(
+                    5,
+                    "Uncanny Dodge",
+                    "Halve the damage of an attack as a reaction.",
+                ),
+                Feature::new(
+                    6,
+                    "Expertise",
+                    "Double proficiency for two more skills or thieves' tools.",
+                ),
+                Feature::new(
+                    7,
+                    "Evasion",
+                    "Take no damage on a successful DEX save for half damage.",
+                ),
+                Feature::new(10, "Ability Score Improvement", ASI),
+                Feature::new(
+                    11,
+                    "Reliable Talent",
+                    "Treat proficient ability check rolls of 9 or lower as 10.",
+                ),
+                Feature::new(
+                    14,
+                    "Blindsense",
+                    "Sense hidden or invisible creatures within 10 feet.",
+                ),
+                Feature::new(
+                    15,
+                    "Slippery Mind",
+                    "Gain proficiency in WIS saving throws.",
+                ),
+                Feature::new(18, "Elusive", "No attack roll has advantage against you."),
+                Feature::new(
+                    20,
+                    "Stroke of Luck",
+                    "Turn a miss into a hit or a failed check into a 20.",
+                ),
+            ],
+            Class::Sorcerer => vec![
+                Feature::new(1, "Spellcasting", "Cast sorcerer spells using CHA."),
+                Feature::new(
+                    1,
+                    "Sorcerous Origin",
+                    "Choose the source of your innate magic.",
+                ),
+                Feature::new(
+                    2,
+                    "Font of Magic",
+                    "Gain sorcery points to convert to and from spell slots.",
+                ),
+                Feature::new(
+                    3,
+                    "Metamagic",
+                    "Learn two Metamagic options to twist your spells.",
+                ),
+                Feature::new(10, "Metamagic", "Learn an additional Metamagic option."),
+                Feature::new(17, "Metamagic", "Learn an additional Metamagic option."),
+                Feature::new(
+                    20,
+                    "Sorcerous Restoration",
+                    "Regain 4 sorcery points on a short rest.",
+                ),
+            ],
+            Class::Warlock => vec![
+                Feature::new(
+                    1,
+                    "Otherworldly Patron",
+                    "Strike a bargain with an otherworldly being.",
+                ),
+                Feature::new(
+                    1,
+                    "Pact Magic",
+                    "Cast warlock spells using CHA with short rest slots.",
+                ),
+                Feature::new(
+                    2,
+                    "Eldritch Invocations",
+                    "Learn fragments of forbidden knowledge.",
+                ),
+                Feature::new(
+                    3,
+                    "Pact Boon",
+                    "Choose the Pact of the Chain, Blade or Tome.",
+                ),
+                Feature::new(
+                    11,
+                    "Mystic Arcanum",
+                    "Cast a 6th level spell once per long rest.",
+                ),
+                Feature::new(
+                    13,
+                    "Mystic Arcanum",
+                    "Cast a 7th level spell once per long rest.",
+                ),
+                Feature::new(
+                    15,
+                    "Mystic Arcanum",
+                    "Cast an 8th level spell once per long rest.",
+                ),
+                Feature::new(
+                    17,
+                    "Mystic Arcanum",
+                    "Cast a 9th level spell once per long rest.",
+                ),
+                Feature::new(
+                    20,
+                    "Eldritch Master",
+                    "Regain all Pact Magic slots once per long rest.",
+                ),
+            ],
+            Class::Wizard => vec![
+                Feature::new(
+                    1,
+                    "Spellcasting",
+                    "Cast wizard spells from your spellbook using INT.",
+                ),
+                Feature::new(
+                    1,
+                    "Arcane Recovery",
+                    "Recover spell slots during a short rest.",
+                ),
+                Feature::new(
+                    2,
+                    "Arcane Tradition",
+                    "Choose a school of magic to specialize in.",
+                ),
+                Feature::new(
+                    18,
+                    "Spell Mastery",
+                    "Cast a chosen 1st and 2nd level spell at will.",
+                ),
+                Feature::new(
+                    20,
+                    "Signature Spells",
+                    "Cast two chosen 3rd level spells once per short rest without a slot.",
+                ),
+            ],
+        };
+
+        for level in [4, 8, 12, 16, 19] {
+            features.push(Feature::new(level, "Ability Score Improvement", ASI));
+        }
+        features.sort_by_key(|feature| feature.level);
+        features
+    }
+
+    /// Bonus damage added to STR melee attacks while raging
+    pub fn rage_damage(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Barbarian => Some(match level {
+                0..=8 => 2,
+                9..=15 => 3,
+                _ => 4,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Rages per long rest, `u8::MAX` meaning unlimited at level 20
+    pub fn rage_uses(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Barbarian => Some(match level {
+                0..=2 => 2,
+                3..=5 => 3,
+                6..=11 => 4,
+                12..=16 => 5,
+                17..=19 => 6,
+                _ => u8::MAX,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn sneak_attack_dice(&self, level: u8) -> Option<Dice> {
+        match self {
+            Class::Rogue => Some(Dice::new(level.div_ceil(2), 6, 0)),
+            _ => None,
+        }
+    }
+
+    /// Ability added on top of DEX by the class's Unarmored Defense
+    pub fn unarmored_defense_ability(&self) -> Option<Abilities> {
+        match self {
+            Class::Barbarian => Some(Abilities::Constitution),
+            Class::Monk => Some(Abilities::Wisdom),
+            _ => None,
+        }
+    }
+
+    pub fn martial_arts_die(&self, level: u8) -> Option<Dice> {
+        match self {
+            Class::Monk => Some(match level {
+                0..=4 => Dice::new(1, 4, 0),
+                5..=10 => Dice::new(1, 6, 0),
+                11..=16 => Dice::new(1, 8, 0),
+                _ => Dice::new(1, 10, 0),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn ki_points(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Monk if level >= 2 => Some(level),
+            _ => None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn bardic_inspiration_die(&self, level: u8) -> Option<Dice> {
+        match self {
+            Class::Bard => Some(match level {
+                0..=4 => Dice::new(1, 6, 0),
+                5..=9 => Dice::new(1, 8, 0),
+                10..=14 => Dice::new(1, 10, 0),
+                _ => Dice::new(1, 12, 0),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn sorcery_points(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Sorcerer if level >= 2 => Some(level),
+            _ => None,
+        }
+    }
+
+    /// Channel Divinity uses between rests
+    pub fn channel_divinity_uses(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Cleric => match level {
+                0..=1 => None,
+                2..=5 => Some(1),
+                6..=17 => Some(2),
+                _ => Some(3),
+            },
+            Class::Paladin if level >= 3 => Some(1),
+            _ => None,
+        }
+    }
+
+    pub fn action_surge_uses(&self, level: u8) -> Option<u8> {
+        match self {
+            Class::Fighter => match level {
+                0..=1 => None,
+                2..=16 => Some(1),
+                _ => Some(2),
+            },
+            _ => None,
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Number of attacks made with the Attack action
+    pub fn attacks_per_action(&self, level: u8) -> u8 {
+        match self {
+            Class::Fighter => match level {
+                0..=4 => 1,
+                5..=10 => 2,
+                11..=19 => 3,
+                _ => 4,
+            },
+            Class::Barbarian | Class::Monk | Class::Paladin | Class::Ranger if level >= 5 => 2,
+            _ => 1,
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{
+    character::Character,
+    class::Class,
+    equipment::{Weapon, WeaponRange},
+    subclass::Subclass,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FightingStyle {
+    Archery,
+    Defense,
+    Dueling,
+    GreatWeaponFighting,
+    /// A reaction on someone else's attack roll, descriptive only
+    Protection,
+    TwoWeaponFighting,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FightingStyleError {
+    NotAvailable(FightingStyle),
+    AlreadyKnown(FightingStyle),
+    NoChoiceLeft,
+}
+
+impl fmt::Display for FightingStyleError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FightingStyleError::NotAvailable(style) => {
+                write!(f, "{} is not available to the character's classes", style)
+            }
+            FightingStyleError::AlreadyKnown(style) => write!(f, "{} is already known", style),
+            FightingStyleError::NoChoiceLeft => write!(f, "No Fighting Style choice left"),
+        }
+    }
+}
+
+impl FightingStyle {
+    pub fn description(&self) -> &'static str {
+        match self {
+            FightingStyle::Archery => "+2 to attack rolls with ranged weapons.",
+            FightingStyle::Defense => "+1 to AC while wearing armor.",
+            FightingStyle::Dueling => "+2 damage with a melee weapon wielded in one hand.",
+            FightingStyle::GreatWeaponFighting => {
+                "Reroll 1s and 2s on damage dice of two-handed melee weapons."
+            }
+            FightingStyle::Protection => {
+                "Impose disadvantage on an attack against an adjacent ally while using a shield."
+            }
+            FightingStyle::TwoWeaponFighting => "Add your ability modifier to off-hand damage.",
+        }
+    }
+}
+
+impl fmt::Display for FightingStyle {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let style = match self {
+            FightingStyle::Archery => "Archery",
+            FightingStyle::Defense => "Defense",
+            FightingStyle::Dueling => "Dueling",
+            FightingStyle::GreatWeaponFighting => "Great Weapon Fighting",
+            FightingStyle::Protection => "Protection",
+            FightingStyle::TwoWeaponFighting => "Two-Weapon Fighting",
+        };
+        write!(f, "{}", style)
+    }
+}
+
+impl Class {
+    /// Fighting Styles the class can pick from at `level`
+    pub fn fighting_styles(&self, level: u8) -> Vec<FightingStyle> {
+        match self {
+            Class::Fighter if level >= 1 => vec![
+                FightingStyle::Archery,
+                FightingStyle::Defense,
+                FightingStyle::Dueling,
+                FightingStyle::GreatWeaponFighting,
+                FightingStyle::Protection,
+                FightingStyle::TwoWeaponFighting,
+            ],
+            Class::Paladin if level >= 2 => vec![
+                FightingStyle::Defense,
+                FightingStyle::Dueling,
+                FightingStyle::GreatWeaponFighting,
+                FightingStyle::Protection,
+            ],
+            Class::Ranger if level >= 2 => vec![
+                FightingStyle::Archery,
+                FightingStyle::Defense,
+                FightingStyle::Dueling,
+                FightingStyle::TwoWeaponFighting,
+            ],
+            _ => vec![],
+        }
+    }
+}
+
+impl Character {
+    /// Number of Fighting Styles the character gets from all classes
+    pub fn fighting_style_count(&self) -> usize {
+        self.classes
+            .iter()
+            .map(|class_level| {
+                let mut count = usize::from(
+                    !class_level
+                        .class
+                        .fighting_styles(class_level.level)
+                        .is_empty(),
+                );
+                // Champion's Additional Fighting Style
+                if class_level.subclass == Some(Subclass::Champion) && class_level.level >= 10 {
+                    count += 1;
+                }
+                count
+            })
+            .sum()
+    }
+
+    pub fn available_fighting_styles(&self) -> Vec<FightingStyle> {
+        let mut styles: Vec<FightingStyle> = Vec::new();
+        for class_level in &self.classes {
+            for style in class_level.class.fighting_styles(class_level.level) {
+                if !styles.contains(&style) {
+                    styles.push(style);
+                }
+            }
+        }
+        styles
+    }
+
+    /// A Fighting Style can't be taken twice, even from another class
+    pub fn choose_fighting_style(
+        &mut self,
+        style: FightingStyle,
+    ) -> Result<(), FightingStyleError> {
+        if self.fighting_styles.contains(&style) {
+            return Err(FightingStyleError::AlreadyKnown(style));
+        }
+        if !self.available_fighting_styles().contains(&style) {
+            return Err(FightingStyleError::NotAvailable(style));
+        }
+        if self.fighting_styles.len() >= self.fighting_style_count() {
+            return Err(FightingStyleError::NoChoiceLeft);
+        }
+
+        self.fighting_styles.push(style);
+        Ok(())
+    }
+
+    pub fn has_fighting_style(&self, style: FightingStyle) -> bool {
+        self.fighting_styles.contains(&style)
+    }
+
+    /// Defense bonus to AC
+    pub fn fighting_style_ac_bonus(&self) -> i16 {
+        if self.has_fighting_style(FightingStyle::Defense) && self.wears_body_armor() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Archery bonus to attack rolls with `weapon`
+    pub fn fighting_style_attack_bonus(&self, weapon: &Weapon) -> i8 {
+        if self.has_fighting_style(FightingStyle::Archery)
+            && weapon.weapon_range == WeaponRange::Ranged
+        {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// Dueling bonus to damage rolls with `weapon` wielded in one hand
+    pub fn fighting_style_damage_bonus(&self, weapon: &Weapon, two_handed_grip: bool) -> i8 {
+        if self.has_fighting_style(FightingStyle::Dueling)
+            && weapon.weapon_range == WeaponRange::Melee
+            && !weapon.is_two_handed()
+            && !two_handed_grip
+        {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// Great Weapon Fighting rerolls 1s and 2s on damage dice
+    pub fn rerolls_low_damage_dice(&self, weapon: &Weapon, two_handed_grip: bool) -> bool {
+        self.has_fighting_style(FightingStyle::GreatWeaponFighting)
+            && weapon.weapon_range == WeaponRange::Melee
+            && (weapon.is_two_handed() || two_handed_grip)
+    }
+
+    /// Two-Weapon Fighting adds the ability modifier to off-hand damage
+    pub fn adds_modifier_to_off_hand_damage(&self) -> bool {
+        self.has_fighting_style(FightingStyle::TwoWeaponFighting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attack::Grip,
+        equipment::Armor,
+        inventory::Inventory,
+        test_support::{character_of, set_levels},
+    };
+
+    fn unequipped_fighter(style: FightingStyle) -> Character {
+        let mut fighter = character_of(Class::Fighter);
+        fighter.inventory = Inventory::default();
+        fighter.fighting_styles.clear();
+        fighter.choose_fighting_style(style).unwrap();
+        fighter
+    }
+
+    fn weapon(name: &str) -> Weapon {
+        Weapon::get_weapon(name).unwrap()
+    }
+
+    #[test]
+    fn one_style_per_class_at_its_level() {
+        let mut fighter = character_of(Class::Fighter);
+        fighter.fighting_styles.clear();
+        assert_eq!(
+            fighter.choose_fighting_style(FightingStyle::Defense),
+            Ok(())
+        );
+        assert_eq!(
+            fighter.choose_fighting_style(FightingStyle::Defense),
+            Err(FightingStyleError::AlreadyKnown(FightingStyle::Defense))
+        );
+        assert_eq!(
+            fighter.choose_fighting_style(FightingStyle::Archery),
+            Err(FightingStyleError::NoChoiceLeft)
+        );
+
+        let mut paladin = character_of(Class::Paladin);
+        assert_eq!(
+            paladin.choose_fighting_style(FightingStyle::Dueling),
+            Err(FightingStyleError::NotAvailable(FightingStyle::Dueling))
+        );
+        set_levels(&mut paladin, &[(Class::Paladin, 2)]);
+        assert_eq!(
+            paladin.choose_fighting_style(FightingStyle::Dueling),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn defense_needs_body_armor() {
+        let mut fighter = unequipped_fighter(FightingStyle::Defense);
+        assert_eq!(fighter.calculate_armor_class(), 10);
+
+        fighter.inventory.body_armor = Armor::get_armor("Chain Mail");
+        assert_eq!(fighter.calculate_armor_class(), 17);
+    }
+
+    #[test]
+    fn archery_adds_to_ranged_attacks_only() {
+        let fighter = unequipped_fighter(FightingStyle::Archery);
+        let longbow = fighter.weapon_attack(&weapon("Longbow"), Grip::TwoHands);
+        assert_eq!(longbow.attack_bonus, 4);
+        let longsword = fighter.weapon_attack(&weapon("Longsword"), Grip::MainHand);
+        assert_eq!(longsword.attack_bonus, 2);
+    }
+
+    #[test]
+    fn dueling_adds_damage_with_one_hand() {
+        let fighter = unequipped_fighter(FightingStyle::Dueling);
+        let longsword = weapon("Longsword");
+        assert_eq!(
+            fighter
+                .weapon_attack(&longsword, Grip::MainHand)
+                .damage
+                .modifier,
+            2
+        );
+        assert_eq!(
+            fighter
+                .weapon_attack(&longsword, Grip::TwoHands)
+                .damage
+                .modifier,
+            0
+        );
+    }
+
+    #[test]
+    fn great_weapon_fighting_needs_both_hands() {
+        let fighter = unequipped_fighter(FightingStyle::GreatWeaponFighting);
+        assert!(
+            fighter
+                .weapon_attack(&weapon("Greatsword"), Grip::TwoHands)
+                .rerolls_low_dice
+        );
+        assert!(
+            !fighter
+                .weapon_attack(&weapon("Longsword"), Grip::MainHand)
+                .rerolls_low_dice
+        );
+    }
+}
@@ -33,10 +33,10 @@ pub fn list_character_files() -> Result<Vec<String>, io::Error> {
         let path = entry.path();
 
         // Only include .json files
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Some(filename) = path.to_str() {
-                files.push(filename.to_string());
-            }
+        if path.extension().and_then(|s| s.to_str()) == Some("json")
+            && let Some(filename) = path.to_str()
+        {
+            files.push(filename.to_string());
         }
     }
 
@@ -47,10 +47,9 @@ pub fn list_character_files() -> Result<Vec<String>, io::Error> {
 pub fn save_character_auto(character: &Character) -> Result<String, io::Error> {
     ensure_characters_directory()?;
 
-    let filename = generate_filename(&character.race, &character.class);
+    let filename = generate_filename(&character.race, &character.primary_class());
 
-    let json = serde_json::to_string_pretty(character)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let json = serde_json::to_string_pretty(character).map_err(io::Error::other)?;
 
     fs::write(&filename, json)?;
 
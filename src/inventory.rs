@@ -0,0 +1,480 @@
+use crate::{
+    character::Character,
+    equipment::{Armor, Gear, Weapon, WeaponProperty, WeaponRange},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Item {
+    Armor(Armor),
+    Weapon(Weapon),
+    Gear(Gear),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquipSlot {
+    BodyArmor,
+    Shield,
+    MainHand,
+    OffHand,
+}
+
+/// What a character wears and wields, everything else is carried
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    pub body_armor: Option<Armor>,
+    pub shield: Option<Armor>,
+    pub main_hand: Option<Weapon>,
+    pub off_hand: Option<Weapon>,
+    pub carried: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    NotCarried(String),
+    WrongSlot {
+        item: String,
+        slot: EquipSlot,
+    },
+    SlotEmpty(EquipSlot),
+    TwoHandedWithShield(String),
+    TwoHandedWithOffHand(String),
+    HandsFull(EquipSlot),
+    /// Fighting with a weapon in each hand needs two Light weapons
+    NotLight(String),
+}
+
+impl fmt::Display for EquipSlot {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            EquipSlot::BodyArmor => write!(f, "Body Armor"),
+            EquipSlot::Shield => write!(f, "Shield"),
+            EquipSlot::MainHand => write!(f, "Main Hand"),
+            EquipSlot::OffHand => write!(f, "Off Hand"),
+        }
+    }
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            InventoryError::NotCarried(item) => write!(f, "{} is not carried", item),
+            InventoryError::WrongSlot { item, slot } => {
+                write!(f, "{} can't go in the {} slot", item, slot)
+            }
+            InventoryError::SlotEmpty(slot) => write!(f, "Nothing equipped in {}", slot),
+            InventoryError::TwoHandedWithShield(weapon) => {
+                write!(f, "{} needs two hands, a shield is equipped", weapon)
+            }
+            InventoryError::TwoHandedWithOffHand(weapon) => {
+                write!(f, "{} needs two hands, the off hand is full", weapon)
+            }
+            InventoryError::HandsFull(slot) => {
+                write!(f, "No free hand for the {} slot", slot)
+            }
+            InventoryError::NotLight(weapon) => {
+                write!(f, "{} is not Light, both hands need Light weapons", weapon)
+            }
+        }
+    }
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Armor(armor) => &armor.name,
+            Item::Weapon(weapon) => &weapon.name,
+            Item::Gear(gear) => &gear.name,
+        }
+    }
+
+    pub fn weight(&self) -> f32 {
+        match self {
+            Item::Armor(armor) => armor.weight,
+            Item::Weapon(weapon) => weapon.weight,
+            Item::Gear(gear) => gear.weight,
+        }
+    }
+
+    pub fn price(&self) -> f32 {
+        match self {
+            Item::Armor(armor) => armor.price,
+            Item::Weapon(weapon) => weapon.price,
+            Item::Gear(gear) => gear.price,
+        }
+    }
+
+    /// Whether the item can be equipped in `slot`, gear never can
+    pub fn fits(&self, slot: EquipSlot) -> bool {
+        match (self, slot) {
+            (Item::Armor(armor), EquipSlot::BodyArmor) => !armor.is_shield(),
+            (Item::Armor(armor), EquipSlot::Shield) => armor.is_shield(),
+            (Item::Weapon(_), EquipSlot::MainHand | EquipSlot::OffHand) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Inventory {
+    /// Equipped items followed by the carried ones
+    pub fn items(&self) -> Vec<Item> {
+        let mut items: Vec<Item> = Vec::new();
+        items.extend(self.body_armor.clone().map(Item::Armor));
+        items.extend(self.shield.clone().map(Item::Armor));
+        items.extend(self.main_hand.clone().map(Item::Weapon));
+        items.extend(self.off_hand.clone().map(Item::Weapon));
+        items.extend(self.carried.iter().cloned());
+        items
+    }
+
+    pub fn weight(&self) -> f32 {
+        self.items().iter().map(|item| item.weight()).sum()
+    }
+
+    pub fn price(&self) -> f32 {
+        self.items().iter().map(|item| item.price()).sum()
+    }
+
+    /// A versatile main hand weapon is held in both hands while the
+    /// other hand is free of a shield and a weapon
+    pub fn two_handed_grip(&self) -> bool {
+        self.shield.is_none()
+            && self.off_hand.is_none()
+            && self
+                .main_hand
+                .as_ref()
+                .is_some_and(|weapon| weapon.is_versatile())
+    }
+
+    fn take_carried(&mut self, name: &str, slot: EquipSlot) -> Result<Item, InventoryError> {
+        let Some(index) = self.carried.iter().position(|item| item.name() == name) else {
+            return Err(InventoryError::NotCarried(name.to_string()));
+        };
+        let Some(index) = self.carried[index..]
+            .iter()
+            .position(|item| item.name() == name && item.fits(slot))
+            .map(|offset| index + offset)
+        else {
+            return Err(InventoryError::WrongSlot {
+                item: name.to_string(),
+                slot,
+            });
+        };
+        Ok(self.carried.remove(index))
+    }
+
+    /// Checks that `item` can go in `slot` next to what is already equipped
+    fn check_hands(&self, item: &Item, slot: EquipSlot) -> Result<(), InventoryError> {
+        let main_hand = match slot {
+            EquipSlot::MainHand => None,
+            _ => self.main_hand.as_ref(),
+        };
+        let is_light = |weapon: &Weapon| weapon.has_property(WeaponProperty::Light);
+        match (item, slot) {
+            (Item::Weapon(weapon), EquipSlot::MainHand) => {
+                if weapon.is_two_handed() && self.shield.is_some() {
+                    return Err(InventoryError::TwoHandedWithShield(weapon.name.clone()));
+                }
+                if weapon.is_two_handed() && self.off_hand.is_some() {
+                    return Err(InventoryError::TwoHandedWithOffHand(weapon.name.clone()));
+                }
+                if self.off_hand.is_some() && !is_light(weapon) {
+                    return Err(InventoryError::NotLight(weapon.name.clone()));
+                }
+            }
+            (Item::Weapon(weapon), EquipSlot::OffHand) => {
+                if weapon.is_two_handed() {
+                    return Err(InventoryError::HandsFull(slot));
+                }
+                if self.shield.is_some() || main_hand.is_some_and(|main| main.is_two_handed()) {
+                    return Err(InventoryError::HandsFull(slot));
+                }
+                if !is_light(weapon) {
+                    return Err(InventoryError::NotLight(weapon.name.clone()));
+                }
+                if let Some(main) = main_hand.filter(|main| !is_light(main)) {
+                    return Err(InventoryError::NotLight(main.name.clone()));
+                }
+            }
+            (Item::Armor(_), EquipSlot::Shield) => {
+                if let Some(main) = main_hand.filter(|main| main.is_two_handed()) {
+                    return Err(InventoryError::TwoHandedWithShield(main.name.clone()));
+                }
+                if self.off_hand.is_some() {
+                    return Err(InventoryError::HandsFull(slot));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn take_slot(&mut self, slot: EquipSlot) -> Option<Item> {
+        match slot {
+            EquipSlot::BodyArmor => self.body_armor.take().map(Item::Armor),
+            EquipSlot::Shield => self.shield.take().map(Item::Armor),
+            EquipSlot::MainHand => self.main_hand.take().map(Item::Weapon),
+            EquipSlot::OffHand => self.off_hand.take().map(Item::Weapon),
+        }
+    }
+
+    fn put(&mut self, item: Item, slot: EquipSlot) {
+        match (item, slot) {
+            (Item::Armor(armor), EquipSlot::BodyArmor) => self.body_armor = Some(armor),
+            (Item::Armor(armor), EquipSlot::Shield) => self.shield = Some(armor),
+            (Item::Weapon(weapon), EquipSlot::MainHand) => self.main_hand = Some(weapon),
+            (Item::Weapon(weapon), EquipSlot::OffHand) => self.off_hand = Some(weapon),
+            (item, _) => self.carried.push(item),
+        }
+    }
+}
+
+impl Character {
+    #[allow(dead_code)]
+    pub fn carry(&mut self, item: Item) {
+        self.inventory.carried.push(item);
+    }
+
+    /// Moves a carried item into `slot`, the item it replaces goes back to
+    /// the carried items. Armor can be worn without proficiency, at the cost
+    /// listed by `armor_without_proficiency`.
+    pub fn equip(&mut self, name: &str, slot: EquipSlot) -> Result<(), InventoryError> {
+        let item = self.inventory.take_carried(name, slot)?;
+        if let Err(error) = self.inventory.check_hands(&item, slot) {
+            self.inventory.carried.push(item);
+            return Err(error);
+        }
+
+        if let Some(replaced) = self.inventory.take_slot(slot) {
+            self.inventory.carried.push(replaced);
+        }
+        self.inventory.put(item, slot);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn unequip(&mut self, slot: EquipSlot) -> Result<(), InventoryError> {
+        let item = self
+            .inventory
+            .take_slot(slot)
+            .ok_or(InventoryError::SlotEmpty(slot))?;
+        self.inventory.carried.push(item);
+        Ok(())
+    }
+
+    /// Puts on the first proficient armor carried and a shield, then the first weapon
+    /// that fits next to the shield, one-handed melee weapons first, and a
+    /// second light weapon when the off hand is still free
+    pub fn equip_carried_gear(&mut self) {
+        let carried = self.inventory.carried.clone();
+        let proficiencies = self.armor_proficiencies();
+        let first_fitting = |slot: EquipSlot| {
+            carried
+                .iter()
+                .find(|item| match item {
+                    Item::Armor(armor) => {
+                        item.fits(slot) && proficiencies.contains(&armor.armor_type)
+                    }
+                    _ => item.fits(slot),
+                })
+                .map(|item| item.name().to_string())
+        };
+
+        if self.inventory.body_armor.is_none()
+            && let Some(name) = first_fitting(EquipSlot::BodyArmor)
+        {
+            let _ = self.equip(&name, EquipSlot::BodyArmor);
+        }
+        if let Some(name) = first_fitting(EquipSlot::Shield) {
+            let _ = self.equip(&name, EquipSlot::Shield);
+        }
+        if self.inventory.main_hand.is_none() {
+            // A two-handed weapon would push the shield back into the pack
+            let has_shield = self.inventory.shield.is_some();
+            let weapons: Vec<&Weapon> = carried
+                .iter()
+                .filter_map(|item| match item {
+                    Item::Weapon(weapon) if !has_shield || !weapon.is_two_handed() => Some(weapon),
+                    _ => None,
+                })
+                .collect();
+            let weapon = weapons
+                .iter()
+                .find(|weapon| !has_shield || weapon.weapon_range == WeaponRange::Melee)
+                .or(weapons.first());
+            if let Some(weapon) = weapon {
+                let _ = self.equip(&weapon.name, EquipSlot::MainHand);
+            }
+        }
+        let main_is_light = self
+            .inventory
+            .main_hand
+            .as_ref()
+            .is_some_and(|weapon| weapon.has_property(WeaponProperty::Light));
+        if main_is_light && self.inventory.off_hand.is_none() {
+            let light = self.inventory.carried.iter().find_map(|item| match item {
+                Item::Weapon(weapon) if weapon.has_property(WeaponProperty::Light) => {
+                    Some(weapon.name.clone())
+                }
+                _ => None,
+            });
+            if let Some(name) = light {
+                let _ = self.equip(&name, EquipSlot::OffHand);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::Class, equipment::ArmorType, spellcasting::SpellcastingError,
+        test_support::character_of,
+    };
+
+    fn fighter_carrying(items: &[Item]) -> Character {
+        let mut character = character_of(Class::Fighter);
+        character.inventory = Inventory {
+            carried: items.to_vec(),
+            ..Inventory::default()
+        };
+        character
+    }
+
+    fn armor(name: &str) -> Item {
+        Item::Armor(Armor::get_armor(name).unwrap())
+    }
+
+    fn weapon(name: &str) -> Item {
+        Item::Weapon(Weapon::get_weapon(name).unwrap())
+    }
+
+    #[test]
+    fn carried_shield_gets_a_one_handed_melee_weapon() {
+        let mut character = fighter_carrying(&[
+            weapon("Longbow"),
+            armor("Shield"),
+            weapon("Longsword"),
+            armor("Chain Mail"),
+        ]);
+        character.equip_carried_gear();
+        let inventory = &character.inventory;
+        assert_eq!(inventory.body_armor.as_ref().unwrap().name, "Chain Mail");
+        assert_eq!(inventory.shield.as_ref().unwrap().name, "Shield");
+        assert_eq!(inventory.main_hand.as_ref().unwrap().name, "Longsword");
+        assert_eq!(inventory.carried.len(), 1);
+    }
+
+    #[test]
+    fn without_a_shield_the_first_weapon_is_wielded() {
+        let mut character = fighter_carrying(&[weapon("Longbow"), weapon("Longsword")]);
+        character.equip_carried_gear();
+        assert_eq!(
+            character.inventory.main_hand.as_ref().unwrap().name,
+            "Longbow"
+        );
+    }
+
+    #[test]
+    fn two_handed_weapons_and_shields_exclude_each_other() {
+        let mut character = fighter_carrying(&[weapon("Longbow"), armor("Shield")]);
+        assert_eq!(character.equip("Shield", EquipSlot::Shield), Ok(()));
+        assert_eq!(
+            character.equip("Longbow", EquipSlot::MainHand),
+            Err(InventoryError::TwoHandedWithShield("Longbow".to_string()))
+        );
+        assert_eq!(
+            character.equip("Shield", EquipSlot::MainHand),
+            Err(InventoryError::NotCarried("Shield".to_string()))
+        );
+        assert_eq!(character.inventory.carried.len(), 1);
+    }
+
+    #[test]
+    fn two_weapons_must_both_be_light() {
+        let mut character = fighter_carrying(&[
+            weapon("Longsword"),
+            weapon("Warhammer"),
+            weapon("Shortsword"),
+            weapon("Dagger"),
+        ]);
+        character.equip("Longsword", EquipSlot::MainHand).unwrap();
+        assert_eq!(
+            character.equip("Warhammer", EquipSlot::OffHand),
+            Err(InventoryError::NotLight("Warhammer".to_string()))
+        );
+        assert_eq!(
+            character.equip("Dagger", EquipSlot::OffHand),
+            Err(InventoryError::NotLight("Longsword".to_string()))
+        );
+
+        character.equip("Shortsword", EquipSlot::MainHand).unwrap();
+        assert_eq!(character.equip("Dagger", EquipSlot::OffHand), Ok(()));
+        assert_eq!(
+            character.equip("Longsword", EquipSlot::MainHand),
+            Err(InventoryError::NotLight("Longsword".to_string()))
+        );
+    }
+
+    #[test]
+    fn armor_can_be_worn_without_proficiency() {
+        let mut character = character_of(Class::Wizard);
+        character.inventory = Inventory {
+            carried: vec![armor("Chain Mail")],
+            ..Inventory::default()
+        };
+        character.equip_carried_gear();
+        assert!(character.inventory.body_armor.is_none());
+
+        assert_eq!(character.equip("Chain Mail", EquipSlot::BodyArmor), Ok(()));
+        assert_eq!(character.calculate_armor_class(), 16);
+        let unproficient: Vec<&str> = character
+            .armor_without_proficiency()
+            .iter()
+            .map(|armor| armor.name.as_str())
+            .collect();
+        assert_eq!(unproficient, vec!["Chain Mail"]);
+
+        let cantrip = character.classes[0].spells.cantrips[0].clone();
+        assert_eq!(
+            character.cast_spell(&cantrip, 0).unwrap_err(),
+            SpellcastingError::ArmorNotProficient("Chain Mail".to_string())
+        );
+    }
+
+    #[test]
+    fn shield_proficiency_comes_from_the_class() {
+        for (class, proficient) in [
+            (Class::Fighter, true),
+            (Class::Cleric, true),
+            (Class::Barbarian, true),
+            (Class::Rogue, false),
+            (Class::Monk, false),
+            (Class::Wizard, false),
+        ] {
+            let character = character_of(class);
+            assert_eq!(
+                character.armor_proficiencies().contains(&ArmorType::Shield),
+                proficient,
+                "{}",
+                class
+            );
+        }
+    }
+
+    #[test]
+    fn shield_adds_two_ac_and_takes_the_versatile_grip() {
+        let mut character = fighter_carrying(&[weapon("Longsword"), armor("Shield")]);
+        character.equip("Longsword", EquipSlot::MainHand).unwrap();
+        assert!(character.inventory.two_handed_grip());
+        assert_eq!(character.weapon_attacks()[0].damage.dice_sides, 10);
+        assert_eq!(character.calculate_armor_class(), 10);
+
+        character.equip("Shield", EquipSlot::Shield).unwrap();
+        assert!(!character.inventory.two_handed_grip());
+        assert_eq!(character.weapon_attacks()[0].damage.dice_sides, 8);
+        assert_eq!(character.calculate_armor_class(), 12);
+    }
+}
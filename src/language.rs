@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Language {
+    // Standard
+    Common,
+    Dwarvish,
+    Elvish,
+    Giant,
+    Gnomish,
+    Goblin,
+    Halfling,
+    Orc,
+    // Exotic
+    Abyssal,
+    Celestial,
+    Draconic,
+    DeepSpeech,
+    Infernal,
+    Primordial,
+    Sylvan,
+    Undercommon,
+    // Secret class languages
+    Druidic,
+    ThievesCant,
+}
+
+impl Language {
+    /// Druidic and Thieves' Cant can only be learned from their class
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Language::Druidic | Language::ThievesCant)
+    }
+
+    /// Languages a character may pick when a source grants "one of your choice"
+    pub fn learnable() -> Vec<Language> {
+        Language::iter()
+            .filter(|language| !language.is_secret())
+            .collect()
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let language = match self {
+            Language::Common => "Common",
+            Language::Dwarvish => "Dwarvish",
+            Language::Elvish => "Elvish",
+            Language::Giant => "Giant",
+            Language::Gnomish => "Gnomish",
+            Language::Goblin => "Goblin",
+            Language::Halfling => "Halfling",
+            Language::Orc => "Orc",
+            Language::Abyssal => "Abyssal",
+            Language::Celestial => "Celestial",
+            Language::Draconic => "Draconic",
+            Language::DeepSpeech => "Deep Speech",
+            Language::Infernal => "Infernal",
+            Language::Primordial => "Primordial",
+            Language::Sylvan => "Sylvan",
+            Language::Undercommon => "Undercommon",
+            Language::Druidic => "Druidic",
+            Language::ThievesCant => "Thieves' Cant",
+        };
+        write!(f, "{}", language)
+    }
+}
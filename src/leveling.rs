@@ -0,0 +1,377 @@
+use crate::{
+    ability::Abilities, character::Character, class::Class, feature::Feature,
+    multiclass::MulticlassError, spell_list::SpellListModel,
+};
+use std::fmt::{self, Formatter};
+
+#[allow(dead_code)]
+/// How hit points are gained on levels after the first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HpIncrease {
+    Rolled,
+    /// Half the hit die plus one, as in the PHB
+    Average,
+}
+
+/// Decisions a level-up leaves for the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingChoice {
+    Subclass(Class),
+    AbilityScoreImprovement(u8),
+    FightingStyle(usize),
+    Cantrips { class: Class, count: usize },
+    Spells { class: Class, count: usize },
+    PactBoon,
+    Invocations(usize),
+}
+
+/// What a single class level added
+#[derive(Debug, Clone)]
+pub struct LevelUp {
+    pub class: Class,
+    pub class_level: u8,
+    pub hp_gained: u16,
+    pub features: Vec<Feature>,
+}
+
+/// Everything an XP award changed
+#[derive(Debug, Clone)]
+pub struct LevelingReport {
+    pub xp_awarded: u32,
+    pub old_level: u8,
+    pub new_level: u8,
+    /// Old and new proficiency bonus
+    pub proficiency_bonus: (i8, i8),
+    pub level_ups: Vec<LevelUp>,
+    /// Why leveling stopped short of the level the XP allows
+    pub stopped: Option<MulticlassError>,
+    pub pending: Vec<PendingChoice>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelingError {
+    NoImprovementLeft,
+    AbilityAtMaximum(Abilities),
+}
+
+impl fmt::Display for PendingChoice {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PendingChoice::Subclass(class) => write!(f, "Choose a {} subclass", class),
+            PendingChoice::AbilityScoreImprovement(count) => {
+                write!(f, "Spend {} Ability Score Improvement(s)", count)
+            }
+            PendingChoice::FightingStyle(count) => write!(f, "Choose {} fighting style(s)", count),
+            PendingChoice::Cantrips { class, count } => {
+                write!(f, "Learn {} {} cantrip(s)", count, class)
+            }
+            PendingChoice::Spells { class, count } => {
+                write!(f, "Learn {} {} spell(s)", count, class)
+            }
+            PendingChoice::PactBoon => write!(f, "Choose a Pact Boon"),
+            PendingChoice::Invocations(count) => {
+                write!(f, "Learn {} Eldritch Invocation(s)", count)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LevelingError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LevelingError::NoImprovementLeft => write!(f, "No Ability Score Improvement left"),
+            LevelingError::AbilityAtMaximum(ability) => {
+                write!(f, "{} can't be raised above 20", ability)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LevelUp {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: +{} HP",
+            self.class, self.class_level, self.hp_gained
+        )?;
+        for feature in &self.features {
+            write!(f, "\n   - {}", feature.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LevelingReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "+{} XP", self.xp_awarded)?;
+        if self.new_level == self.old_level {
+            return write!(f, ", still level {}", self.new_level);
+        }
+        write!(f, ", level {} → {}", self.old_level, self.new_level)?;
+        for level_up in &self.level_ups {
+            write!(f, "\n • {}", level_up)?;
+        }
+        let (old_bonus, new_bonus) = self.proficiency_bonus;
+        if old_bonus != new_bonus {
+            write!(
+                f,
+                "\n • Proficiency bonus {:+} → {:+}",
+                old_bonus, new_bonus
+            )?;
+        }
+        if let Some(error) = &self.stopped {
+            write!(f, "\n • Leveling stopped: {}", error)?;
+        }
+        for choice in &self.pending {
+            write!(f, "\n • Pending: {}", choice)?;
+        }
+        Ok(())
+    }
+}
+
+impl HpIncrease {
+    pub fn hp_roll(&self, class: Class) -> u8 {
+        let hit_die = class.get_class_hit_dice();
+        match self {
+            HpIncrease::Rolled => hit_die.roll(),
+            HpIncrease::Average => hit_die.dice_sides / 2 + 1,
+        }
+    }
+}
+
+impl Character {
+    #[allow(dead_code)]
+    /// Adds XP and levels up once per level the new total reaches. Every
+    /// level goes to `primary_class()`, levels in other classes are taken
+    /// with `add_class_level` instead. An error from `add_class_level` stops
+    /// leveling and is kept in the report.
+    pub fn award_xp(&mut self, xp: u32, hp: HpIncrease) -> LevelingReport {
+        let old_level = self.level();
+        let old_bonus = self.proficiency_bonus();
+        self.current_xp = self.current_xp.saturating_add(xp);
+
+        let mut level_ups = Vec::new();
+        let mut stopped = None;
+        while self.level() < self.calculate_current_level() {
+            match self.add_class_level(self.primary_class(), &[], hp) {
+                Ok(level_up) => level_ups.push(level_up),
+                Err(error) => {
+                    stopped = Some(error);
+                    break;
+                }
+            }
+        }
+
+        LevelingReport {
+            xp_awarded: xp,
+            old_level,
+            new_level: self.level(),
+            proficiency_bonus: (old_bonus, self.proficiency_bonus()),
+            level_ups,
+            stopped,
+            pending: self.pending_choices(),
+        }
+    }
+
+    /// Class and subclass features gained at exactly `level` in `class`
+    pub fn features_at(&self, class: Class, level: u8) -> Vec<Feature> {
+        let subclass = self
+            .classes
+            .iter()
+            .find(|class_level| class_level.class == class)
+            .and_then(|class_level| class_level.subclass);
+        class
+            .features()
+            .into_iter()
+            .chain(subclass.map(|s| s.features()).unwrap_or_default())
+            .filter(|feature| feature.level == level)
+            .collect()
+    }
+
+    /// Choices still open at the current levels
+    pub fn pending_choices(&self) -> Vec<PendingChoice> {
+        let mut pending = Vec::new();
+        for class_level in &self.classes {
+            if class_level.subclass.is_none()
+                && class_level.level >= class_level.class.subclass_level()
+            {
+                pending.push(PendingChoice::Subclass(class_level.class));
+            }
+        }
+        if self.ability_score_improvements > 0 {
+            pending.push(PendingChoice::AbilityScoreImprovement(
+                self.ability_score_improvements,
+            ));
+        }
+        let styles = self
+            .fighting_style_count()
+            .saturating_sub(self.fighting_styles.len());
+        if styles > 0 {
+            pending.push(PendingChoice::FightingStyle(styles));
+        }
+
+        for class_level in &self.classes {
+            let class = class_level.class;
+            let spells = &class_level.spells;
+            let cantrips =
+                (class_level.cantrip_limit() as usize).saturating_sub(spells.cantrips.len());
+            if cantrips > 0 {
+                pending.push(PendingChoice::Cantrips {
+                    class,
+                    count: cantrips,
+                });
+            }
+            let missing = match class_level.spell_list_model() {
+                Some(SpellListModel::Known) => class_level.known_limit().map_or(0, |limit| {
+                    (limit as usize).saturating_sub(spells.known.len())
+                }),
+                Some(SpellListModel::Spellbook) => (class_level.free_spellbook_spells() as usize)
+                    .saturating_sub(spells.spellbook.len()),
+                _ => 0,
+            };
+            if missing > 0 {
+                pending.push(PendingChoice::Spells {
+                    class,
+                    count: missing,
+                });
+            }
+        }
+
+        let warlock_level = self.class_level(Class::Warlock);
+        if warlock_level >= 3 && self.pact_boon.is_none() {
+            pending.push(PendingChoice::PactBoon);
+        }
+        let invocations = (Class::Warlock.invocations_known(warlock_level) as usize)
+            .saturating_sub(self.invocations.len());
+        if invocations > 0 {
+            pending.push(PendingChoice::Invocations(invocations));
+        }
+        pending
+    }
+
+    #[allow(dead_code)]
+    /// Spends an Ability Score Improvement on +1 to two abilities, or +2
+    /// to one when both are the same. Scores can't go above 20.
+    pub fn improve_ability_scores(
+        &mut self,
+        first: Abilities,
+        second: Abilities,
+    ) -> Result<(), LevelingError> {
+        if self.ability_score_improvements == 0 {
+            return Err(LevelingError::NoImprovementLeft);
+        }
+        for ability in [first, second] {
+            let increase = if first == second { 2 } else { 1 };
+            if self.stats.get(ability) + increase > 20 {
+                return Err(LevelingError::AbilityAtMaximum(ability));
+            }
+        }
+
+        self.stats.change_ability_value(first, 1);
+        self.stats.change_ability_value(second, 1);
+        self.ability_score_improvements -= 1;
+
+        // A higher CON modifier raises HP for every level
+        self.raise_max_hp();
+        self.refresh_resources();
+        Ok(())
+    }
+
+    /// Recalculates the hit point maximum, current HP goes up by the gain
+    /// unless the character is down at 0 HP. Returns the gain.
+    pub fn raise_max_hp(&mut self) -> u16 {
+        let old_max_hp = self.max_hp;
+        self.calculate_max_hp();
+        let gained = self.max_hp.saturating_sub(old_max_hp);
+        if self.current_hp > 0 {
+            self.current_hp += gained;
+        }
+        gained
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::character_of;
+
+    fn fighter() -> Character {
+        let mut fighter = character_of(Class::Fighter);
+        fighter.fighting_styles.clear();
+        fighter
+    }
+
+    #[test]
+    fn xp_levels_up_the_primary_class() {
+        let mut fighter = fighter();
+        let report = fighter.award_xp(2_700, HpIncrease::Average);
+        assert_eq!((report.old_level, report.new_level), (1, 4));
+        assert_eq!(report.proficiency_bonus, (2, 2));
+        assert_eq!(report.stopped, None);
+        let levels: Vec<u8> = report
+            .level_ups
+            .iter()
+            .map(|level_up| level_up.class_level)
+            .collect();
+        assert_eq!(levels, vec![2, 3, 4]);
+        assert!(
+            report.level_ups[0]
+                .features
+                .iter()
+                .any(|feature| feature.name == "Action Surge")
+        );
+        // d10 hit die averages 6 per level with CON 10
+        assert_eq!(fighter.max_hp, 28);
+        assert!(
+            report
+                .pending
+                .contains(&PendingChoice::Subclass(Class::Fighter))
+        );
+        assert!(
+            report
+                .pending
+                .contains(&PendingChoice::AbilityScoreImprovement(1))
+        );
+        assert!(report.pending.contains(&PendingChoice::FightingStyle(1)));
+
+        let report = fighter.award_xp(3_800, HpIncrease::Average);
+        assert_eq!(report.new_level, 5);
+        assert_eq!(report.proficiency_bonus, (2, 3));
+    }
+
+    #[test]
+    fn too_little_xp_changes_nothing() {
+        let mut fighter = fighter();
+        let report = fighter.award_xp(299, HpIncrease::Rolled);
+        assert_eq!(report.new_level, 1);
+        assert!(report.level_ups.is_empty());
+        assert_eq!(fighter.current_xp, 299);
+    }
+
+    #[test]
+    fn ability_score_improvement_is_capped_at_20() {
+        let mut fighter = fighter();
+        assert_eq!(
+            fighter.improve_ability_scores(Abilities::Strength, Abilities::Strength),
+            Err(LevelingError::NoImprovementLeft)
+        );
+
+        fighter.award_xp(2_700, HpIncrease::Average);
+        fighter.stats.set(Abilities::Strength, 19);
+        assert_eq!(
+            fighter.improve_ability_scores(Abilities::Strength, Abilities::Strength),
+            Err(LevelingError::AbilityAtMaximum(Abilities::Strength))
+        );
+
+        fighter.stats.set(Abilities::Constitution, 11);
+        assert_eq!(
+            fighter.improve_ability_scores(Abilities::Constitution, Abilities::Constitution),
+            Ok(())
+        );
+        assert_eq!(fighter.stats.get(Abilities::Constitution), 13);
+        assert_eq!(fighter.ability_score_improvements, 0);
+        // +1 CON modifier on each of the 4 levels
+        assert_eq!(fighter.max_hp, 32);
+        assert_eq!(fighter.current_hp, 32);
+    }
+}
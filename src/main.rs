@@ -1,24 +1,64 @@
 mod ability;
+mod armor_class;
+mod attack;
+mod background;
+mod casting;
 mod character;
 mod class;
+mod concentration;
+mod damage;
+mod death_save;
 mod dice;
 mod equipment;
 mod experience;
+mod feature;
+mod fighting_style;
 mod fileio;
+mod inventory;
+mod language;
+mod leveling;
 mod money;
+mod multiclass;
+mod pact_magic;
+mod proficiency;
 mod race;
+mod resource;
+mod rest;
 mod skill;
+mod spell;
+mod spell_list;
+mod spellcasting;
+mod starting_equipment;
+mod subclass;
+#[cfg(test)]
+mod test_support;
+mod tool;
 
-use crate::{character::Character, class::Class, race::Race};
+use crate::{background::Background, character::Character, class::Class, race::Race};
 use std::io;
 
 fn main() -> Result<(), io::Error> {
     println!("=== D&D Character Generator ===\n");
 
     // Create a few characters
-    let barb = Character::new("Anja".to_string(), Race::Elf, Class::Barbarian);
-    let fighter = Character::new("Thor".to_string(), Race::Dwarf, Class::Fighter);
-    let rogue = Character::new("Glog".to_string(), Race::HalfElf, Class::Rogue);
+    let barb = Character::new(
+        "Anja".to_string(),
+        Race::Elf,
+        Class::Barbarian,
+        Background::Outlander,
+    )?;
+    let fighter = Character::new(
+        "Thor".to_string(),
+        Race::Dwarf,
+        Class::Fighter,
+        Background::Soldier,
+    )?;
+    let rogue = Character::new(
+        "Glog".to_string(),
+        Race::HalfElf,
+        Class::Rogue,
+        Background::Criminal,
+    )?;
 
     // Save them all
     // println!("Saving characters...");
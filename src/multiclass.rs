@@ -0,0 +1,358 @@
+use crate::{
+    ability::{Abilities, AbilityScores},
+    character::Character,
+    class::Class,
+    equipment::{ArmorType, Weapon, WeaponProficiency, WeaponType},
+    experience::XP_TABLE,
+    leveling::{HpIncrease, LevelUp},
+    skill::{Skill, SkillChoiceError},
+    spell_list::SpellList,
+    subclass::Subclass,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+/// Levels a character has in one class. `hp_rolls` holds the hit die result
+/// of every level taken in the class, the CON modifier is added on top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassLevel {
+    pub class: Class,
+    pub level: u8,
+    pub subclass: Option<Subclass>,
+    pub hp_rolls: Vec<u8>,
+    pub spells: SpellList,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MulticlassError {
+    MaxLevel,
+    NotEnoughXp { required: u32, current: u32 },
+    PrerequisiteNotMet(Class),
+    Skills(SkillChoiceError),
+}
+
+impl fmt::Display for MulticlassError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MulticlassError::MaxLevel => write!(f, "Character is already level 20"),
+            MulticlassError::NotEnoughXp { required, current } => {
+                write!(
+                    f,
+                    "Next level needs {} XP, character has {}",
+                    required, current
+                )
+            }
+            MulticlassError::PrerequisiteNotMet(class) => {
+                write!(f, "Ability prerequisites for {} are not met", class)
+            }
+            MulticlassError::Skills(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl ClassLevel {
+    pub fn new(class: Class, first_hp_roll: u8) -> Self {
+        ClassLevel {
+            class,
+            level: 1,
+            subclass: None,
+            hp_rolls: vec![first_hp_roll],
+            spells: SpellList::default(),
+        }
+    }
+}
+
+impl Class {
+    /// PHB multiclassing prerequisites, every ability listed needs a score of 13
+    pub fn multiclass_prerequisites(&self) -> Vec<Vec<Abilities>> {
+        // Outer list is AND, inner list is OR
+        match self {
+            Class::Barbarian => vec![vec![Abilities::Strength]],
+            Class::Bard => vec![vec![Abilities::Charisma]],
+            Class::Cleric => vec![vec![Abilities::Wisdom]],
+            Class::Druid => vec![vec![Abilities::Wisdom]],
+            Class::Fighter => vec![vec![Abilities::Strength, Abilities::Dexterity]],
+            Class::Monk => vec![vec![Abilities::Dexterity], vec![Abilities::Wisdom]],
+            Class::Paladin => vec![vec![Abilities::Strength], vec![Abilities::Charisma]],
+            Class::Ranger => vec![vec![Abilities::Dexterity], vec![Abilities::Wisdom]],
+            Class::Rogue => vec![vec![Abilities::Dexterity]],
+            Class::Sorcerer => vec![vec![Abilities::Charisma]],
+            Class::Warlock => vec![vec![Abilities::Charisma]],
+            Class::Wizard => vec![vec![Abilities::Intelligence]],
+        }
+    }
+
+    pub fn meets_multiclass_prerequisites(&self, stats: &AbilityScores) -> bool {
+        self.multiclass_prerequisites()
+            .iter()
+            .all(|any_of| any_of.iter().any(|ability| stats.get(*ability) >= 13))
+    }
+
+    /// Armor proficiencies gained when taking the class as a later class
+    pub fn multiclass_armor_proficiency(&self) -> Vec<ArmorType> {
+        match self {
+            Class::Bard | Class::Rogue | Class::Warlock => vec![ArmorType::Light],
+            Class::Barbarian => vec![ArmorType::Shield],
+            Class::Cleric | Class::Druid | Class::Fighter | Class::Paladin | Class::Ranger => {
+                vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
+            }
+            Class::Monk | Class::Sorcerer | Class::Wizard => vec![],
+        }
+    }
+
+    /// Weapon proficiencies gained when taking the class as a later class
+    pub fn multiclass_weapon_proficiency(&self) -> Vec<WeaponProficiency> {
+        match self {
+            Class::Barbarian | Class::Fighter | Class::Paladin | Class::Ranger | Class::Monk => {
+                // Monks also bring shortswords along with simple weapons
+                self.get_weapon_proficieny()
+            }
+            Class::Warlock => vec![WeaponProficiency::Category(WeaponType::Simple)],
+            _ => vec![],
+        }
+    }
+
+    /// Skills picked when taking the class as a later class
+    pub fn multiclass_skill_count(&self) -> usize {
+        match self {
+            Class::Bard | Class::Ranger | Class::Rogue => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl Character {
+    pub fn can_multiclass_into(&self, class: Class) -> bool {
+        self.classes
+            .iter()
+            .map(|class_level| class_level.class)
+            .chain(std::iter::once(class))
+            .all(|class| class.meets_multiclass_prerequisites(&self.stats))
+    }
+
+    /// Adds a level in `class`. Taking a new class checks the ability
+    /// prerequisites and grants only its multiclass proficiencies, `skills`
+    /// being the picks for classes that grant one.
+    pub fn add_class_level(
+        &mut self,
+        class: Class,
+        skills: &[Skill],
+        hp: HpIncrease,
+    ) -> Result<LevelUp, MulticlassError> {
+        let level = self.level();
+        if level >= 20 {
+            return Err(MulticlassError::MaxLevel);
+        }
+        let required = XP_TABLE[level as usize + 1];
+        if self.current_xp < required {
+            return Err(MulticlassError::NotEnoughXp {
+                required,
+                current: self.current_xp,
+            });
+        }
+
+        let hp_roll = hp.hp_roll(class);
+
+        if let Some(class_level) = self.classes.iter_mut().find(|c| c.class == class) {
+            class_level.level += 1;
+            class_level.hp_rolls.push(hp_roll);
+        } else {
+            if !self.can_multiclass_into(class) {
+                return Err(MulticlassError::PrerequisiteNotMet(class));
+            }
+            self.learn_multiclass_skills(class, skills)
+                .map_err(MulticlassError::Skills)?;
+            self.learn_multiclass_tools(class, rand::random());
+            self.classes.push(ClassLevel::new(class, hp_roll));
+        }
+
+        let hp_gained = self.raise_max_hp();
+        self.refresh_resources();
+        self.refresh_spell_slots();
+        self.refresh_pact_slots();
+        self.refresh_hit_dice();
+
+        let class_level = self.class_level(class);
+        let features = self.features_at(class, class_level);
+        let improvements = features
+            .iter()
+            .filter(|feature| feature.name == "Ability Score Improvement")
+            .count();
+        self.ability_score_improvements += improvements as u8;
+        Ok(LevelUp {
+            class,
+            class_level,
+            hp_gained,
+            features,
+        })
+    }
+
+    /// Full armor proficiencies of the starting class plus the multiclass
+    /// proficiencies of every later class
+    pub fn armor_proficiencies(&self) -> Vec<ArmorType> {
+        let mut proficiencies = self.primary_class().get_class_armor_proficiency();
+        for class_level in self.classes.iter().skip(1) {
+            for armor_type in class_level.class.multiclass_armor_proficiency() {
+                if !proficiencies.contains(&armor_type) {
+                    proficiencies.push(armor_type);
+                }
+            }
+        }
+        proficiencies
+    }
+
+    /// Weapon proficiencies from race, the starting class and the
+    /// multiclass proficiencies of every later class
+    pub fn weapon_proficiencies(&self) -> Vec<WeaponProficiency> {
+        let mut proficiencies = self.primary_class().get_weapon_proficieny();
+        let later_classes = self
+            .classes
+            .iter()
+            .skip(1)
+            .flat_map(|class_level| class_level.class.multiclass_weapon_proficiency());
+        for proficiency in later_classes.chain(self.race.weapon_proficiencies()) {
+            if !proficiencies.contains(&proficiency) {
+                proficiencies.push(proficiency);
+            }
+        }
+        proficiencies
+    }
+
+    pub fn is_proficient_with(&self, weapon: &Weapon) -> bool {
+        self.weapon_proficiencies()
+            .iter()
+            .any(|proficiency| proficiency.covers(weapon))
+    }
+
+    fn learn_multiclass_skills(
+        &mut self,
+        class: Class,
+        skills: &[Skill],
+    ) -> Result<(), SkillChoiceError> {
+        let expected = class.multiclass_skill_count();
+        if skills.len() != expected {
+            return Err(SkillChoiceError::WrongCount {
+                expected,
+                got: skills.len(),
+            });
+        }
+
+        let allowed = class.allowed_skills();
+        for (index, skill) in skills.iter().enumerate() {
+            if self.skills.contains(skill) || skills[..index].contains(skill) {
+                return Err(SkillChoiceError::Duplicate(*skill));
+            }
+            if !allowed.contains(skill) {
+                return Err(SkillChoiceError::NotAllowed(*skill));
+            }
+        }
+
+        self.skills.extend_from_slice(skills);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character_of, set_abilities};
+
+    fn wizard_with_xp(score: u8) -> Character {
+        let mut wizard = character_of(Class::Wizard);
+        set_abilities(&mut wizard, score);
+        wizard.current_xp = XP_TABLE[2];
+        wizard
+    }
+
+    #[test]
+    fn new_class_needs_xp_and_prerequisites() {
+        let mut wizard = wizard_with_xp(10);
+        wizard.current_xp = 0;
+        assert_eq!(
+            wizard
+                .add_class_level(Class::Fighter, &[], HpIncrease::Average)
+                .err(),
+            Some(MulticlassError::NotEnoughXp {
+                required: 300,
+                current: 0
+            })
+        );
+
+        wizard.current_xp = XP_TABLE[2];
+        assert_eq!(
+            wizard
+                .add_class_level(Class::Fighter, &[], HpIncrease::Average)
+                .err(),
+            Some(MulticlassError::PrerequisiteNotMet(Class::Fighter))
+        );
+        assert_eq!(wizard.level(), 1);
+    }
+
+    #[test]
+    fn later_class_grants_multiclass_proficiencies_only() {
+        let mut wizard = wizard_with_xp(13);
+        wizard
+            .add_class_level(Class::Fighter, &[], HpIncrease::Average)
+            .unwrap();
+
+        assert_eq!(wizard.level(), 2);
+        assert_eq!(wizard.primary_class(), Class::Wizard);
+        assert_eq!(
+            wizard.armor_proficiencies(),
+            vec![ArmorType::Light, ArmorType::Medium, ArmorType::Shield]
+        );
+        assert!(
+            wizard
+                .weapon_proficiencies()
+                .contains(&WeaponProficiency::Category(WeaponType::Martial))
+        );
+        // Wizard d6 and Fighter average 6, CON 13 adds 1 per level
+        assert_eq!(wizard.max_hp, 14);
+        let dice: Vec<(u8, u8)> = wizard
+            .hit_dice
+            .iter()
+            .map(|pool| (pool.sides, pool.max))
+            .collect();
+        assert_eq!(dice, vec![(10, 1), (6, 1)]);
+    }
+
+    #[test]
+    fn later_class_skill_pick_comes_from_its_list() {
+        let mut wizard = wizard_with_xp(13);
+        assert_eq!(
+            wizard
+                .add_class_level(Class::Rogue, &[], HpIncrease::Average)
+                .err(),
+            Some(MulticlassError::Skills(SkillChoiceError::WrongCount {
+                expected: 1,
+                got: 0
+            }))
+        );
+        assert_eq!(
+            wizard
+                .add_class_level(Class::Rogue, &[Skill::AnimalHandling], HpIncrease::Average)
+                .err(),
+            Some(MulticlassError::Skills(SkillChoiceError::NotAllowed(
+                Skill::AnimalHandling
+            )))
+        );
+
+        let pick = Class::Rogue
+            .allowed_skills()
+            .into_iter()
+            .find(|skill| !wizard.skills.contains(skill))
+            .unwrap();
+        wizard
+            .add_class_level(Class::Rogue, &[pick], HpIncrease::Average)
+            .unwrap();
+        assert!(wizard.skills.contains(&pick));
+    }
+
+    #[test]
+    fn saved_character_needs_a_class() {
+        let wizard = character_of(Class::Wizard);
+        let mut json = serde_json::to_value(&wizard).unwrap();
+        json["classes"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<Character>(json).is_err());
+    }
+}
@@ -0,0 +1,499 @@
+use crate::{character::Character, class::Class, skill::Skill, spellcasting::SpellcastingError};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Warlock slots, all of the same level and regained on a short rest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PactSlots {
+    pub slot_level: u8,
+    pub current: u8,
+    pub max: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum PactBoon {
+    Chain,
+    Blade,
+    Tome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum Invocation {
+    AgonizingBlast,
+    ArmorOfShadows,
+    AscendantStep,
+    BeastSpeech,
+    BeguilingInfluence,
+    BewitchingWhispers,
+    BookOfAncientSecrets,
+    ChainsOfCarceri,
+    DevilsSight,
+    DreadfulWord,
+    EldritchSight,
+    EldritchSpear,
+    EyesOfTheRuneKeeper,
+    FiendishVigor,
+    GazeOfTwoMinds,
+    Lifedrinker,
+    MaskOfManyFaces,
+    MasterOfMyriadForms,
+    MinionsOfChaos,
+    MireTheMind,
+    MistyVisions,
+    OneWithShadows,
+    OtherworldlyLeap,
+    RepellingBlast,
+    SculptorOfFlesh,
+    SignOfIllOmen,
+    ThiefOfFiveFates,
+    ThirstingBlade,
+    VisionsOfDistantRealms,
+    VoiceOfTheChainMaster,
+    WhispersOfTheGrave,
+    WitchSight,
+}
+
+/// What a warlock needs before taking an invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvocationPrerequisite {
+    pub level: u8,
+    pub pact: Option<PactBoon>,
+    pub cantrip: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PactMagicError {
+    NotAWarlock,
+    LevelTooLow { required: u8, current: u8 },
+    PactBoonAlreadyChosen(PactBoon),
+    WrongPact(PactBoon),
+    MissingCantrip(&'static str),
+    AlreadyKnown(Invocation),
+    NoInvocationLeft(u8),
+}
+
+impl fmt::Display for PactMagicError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PactMagicError::NotAWarlock => write!(f, "Character has no warlock levels"),
+            PactMagicError::LevelTooLow { required, current } => write!(
+                f,
+                "Requires warlock level {}, character has {}",
+                required, current
+            ),
+            PactMagicError::PactBoonAlreadyChosen(boon) => {
+                write!(f, "{} is already chosen", boon)
+            }
+            PactMagicError::WrongPact(boon) => write!(f, "Requires the {}", boon),
+            PactMagicError::MissingCantrip(cantrip) => {
+                write!(f, "Requires the {} cantrip", cantrip)
+            }
+            PactMagicError::AlreadyKnown(invocation) => {
+                write!(f, "{} is already known", invocation)
+            }
+            PactMagicError::NoInvocationLeft(limit) => {
+                write!(f, "Already knows {} invocations", limit)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PactBoon {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PactBoon::Chain => write!(f, "Pact of the Chain"),
+            PactBoon::Blade => write!(f, "Pact of the Blade"),
+            PactBoon::Tome => write!(f, "Pact of the Tome"),
+        }
+    }
+}
+
+impl fmt::Display for PactSlots {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let level = match self.slot_level {
+            1 => "1st",
+            2 => "2nd",
+            3 => "3rd",
+            4 => "4th",
+            _ => "5th",
+        };
+        write!(f, "{} {}/{} (Short Rest)", level, self.current, self.max)
+    }
+}
+
+impl Class {
+    /// Pact Magic slot count and slot level
+    pub fn pact_magic_slots(&self, level: u8) -> Option<(u8, u8)> {
+        if *self != Class::Warlock || level == 0 {
+            return None;
+        }
+        let count = match level {
+            1 => 1,
+            2..=10 => 2,
+            11..=16 => 3,
+            _ => 4,
+        };
+        Some((count, level.div_ceil(2).min(5)))
+    }
+
+    pub fn invocations_known(&self, level: u8) -> u8 {
+        match (self, level) {
+            (Class::Warlock, 2..=4) => 2,
+            (Class::Warlock, 5..=6) => 3,
+            (Class::Warlock, 7..=8) => 4,
+            (Class::Warlock, 9..=11) => 5,
+            (Class::Warlock, 12..=14) => 6,
+            (Class::Warlock, 15..=17) => 7,
+            (Class::Warlock, 18..) => 8,
+            _ => 0,
+        }
+    }
+}
+
+impl Invocation {
+    pub fn prerequisite(&self) -> InvocationPrerequisite {
+        let (level, pact, cantrip) = match self {
+            Invocation::AgonizingBlast | Invocation::EldritchSpear | Invocation::RepellingBlast => {
+                (0, None, Some("Eldritch Blast"))
+            }
+            Invocation::BookOfAncientSecrets => (0, Some(PactBoon::Tome), None),
+            Invocation::VoiceOfTheChainMaster => (0, Some(PactBoon::Chain), None),
+            Invocation::MireTheMind | Invocation::OneWithShadows | Invocation::SignOfIllOmen => {
+                (5, None, None)
+            }
+            Invocation::ThirstingBlade => (5, Some(PactBoon::Blade), None),
+            Invocation::BewitchingWhispers
+            | Invocation::DreadfulWord
+            | Invocation::SculptorOfFlesh => (7, None, None),
+            Invocation::AscendantStep
+            | Invocation::MinionsOfChaos
+            | Invocation::OtherworldlyLeap
+            | Invocation::WhispersOfTheGrave => (9, None, None),
+            Invocation::Lifedrinker => (12, Some(PactBoon::Blade), None),
+            Invocation::ChainsOfCarceri => (15, Some(PactBoon::Chain), None),
+            Invocation::MasterOfMyriadForms
+            | Invocation::VisionsOfDistantRealms
+            | Invocation::WitchSight => (15, None, None),
+            _ => (0, None, None),
+        };
+        InvocationPrerequisite {
+            level,
+            pact,
+            cantrip,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Invocation::AgonizingBlast => "Add CHA to Eldritch Blast damage.",
+            Invocation::ArmorOfShadows => "Cast Mage Armor on yourself at will.",
+            Invocation::AscendantStep => "Cast Levitate on yourself at will.",
+            Invocation::BeastSpeech => "Cast Speak with Animals at will.",
+            Invocation::BeguilingInfluence => "Proficiency in Deception and Persuasion.",
+            Invocation::BewitchingWhispers => "Cast Compulsion once per long rest.",
+            Invocation::BookOfAncientSecrets => "Inscribe rituals in your Book of Shadows.",
+            Invocation::ChainsOfCarceri => {
+                "Cast Hold Monster at will on celestials, fiends and elementals."
+            }
+            Invocation::DevilsSight => "See normally in darkness, magical or not, to 120 feet.",
+            Invocation::DreadfulWord => "Cast Confusion once per long rest.",
+            Invocation::EldritchSight => "Cast Detect Magic at will.",
+            Invocation::EldritchSpear => "Eldritch Blast has a range of 300 feet.",
+            Invocation::EyesOfTheRuneKeeper => "Read all writing.",
+            Invocation::FiendishVigor => {
+                "Cast False Life on yourself at will as a 1st-level spell."
+            }
+            Invocation::GazeOfTwoMinds => "Perceive through a willing humanoid's senses.",
+            Invocation::Lifedrinker => "Pact weapon hits deal extra necrotic damage equal to CHA.",
+            Invocation::MaskOfManyFaces => "Cast Disguise Self at will.",
+            Invocation::MasterOfMyriadForms => "Cast Alter Self at will.",
+            Invocation::MinionsOfChaos => "Cast Conjure Elemental once per long rest.",
+            Invocation::MireTheMind => "Cast Slow once per long rest.",
+            Invocation::MistyVisions => "Cast Silent Image at will.",
+            Invocation::OneWithShadows => "Become invisible in dim light or darkness.",
+            Invocation::OtherworldlyLeap => "Cast Jump on yourself at will.",
+            Invocation::RepellingBlast => "Eldritch Blast pushes the target 10 feet away.",
+            Invocation::SculptorOfFlesh => "Cast Polymorph once per long rest.",
+            Invocation::SignOfIllOmen => "Cast Bestow Curse once per long rest.",
+            Invocation::ThiefOfFiveFates => "Cast Bane once per long rest.",
+            Invocation::ThirstingBlade => "Attack twice with your pact weapon.",
+            Invocation::VisionsOfDistantRealms => "Cast Arcane Eye at will.",
+            Invocation::VoiceOfTheChainMaster => {
+                "Communicate through and perceive with your familiar."
+            }
+            Invocation::WhispersOfTheGrave => "Cast Speak with Dead at will.",
+            Invocation::WitchSight => {
+                "See the true form of shapechangers and illusions within 30 feet."
+            }
+        }
+    }
+}
+
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let invocation = match self {
+            Invocation::AgonizingBlast => "Agonizing Blast",
+            Invocation::ArmorOfShadows => "Armor of Shadows",
+            Invocation::AscendantStep => "Ascendant Step",
+            Invocation::BeastSpeech => "Beast Speech",
+            Invocation::BeguilingInfluence => "Beguiling Influence",
+            Invocation::BewitchingWhispers => "Bewitching Whispers",
+            Invocation::BookOfAncientSecrets => "Book of Ancient Secrets",
+            Invocation::ChainsOfCarceri => "Chains of Carceri",
+            Invocation::DevilsSight => "Devil's Sight",
+            Invocation::DreadfulWord => "Dreadful Word",
+            Invocation::EldritchSight => "Eldritch Sight",
+            Invocation::EldritchSpear => "Eldritch Spear",
+            Invocation::EyesOfTheRuneKeeper => "Eyes of the Rune Keeper",
+            Invocation::FiendishVigor => "Fiendish Vigor",
+            Invocation::GazeOfTwoMinds => "Gaze of Two Minds",
+            Invocation::Lifedrinker => "Lifedrinker",
+            Invocation::MaskOfManyFaces => "Mask of Many Faces",
+            Invocation::MasterOfMyriadForms => "Master of Myriad Forms",
+            Invocation::MinionsOfChaos => "Minions of Chaos",
+            Invocation::MireTheMind => "Mire the Mind",
+            Invocation::MistyVisions => "Misty Visions",
+            Invocation::OneWithShadows => "One with Shadows",
+            Invocation::OtherworldlyLeap => "Otherworldly Leap",
+            Invocation::RepellingBlast => "Repelling Blast",
+            Invocation::SculptorOfFlesh => "Sculptor of Flesh",
+            Invocation::SignOfIllOmen => "Sign of Ill Omen",
+            Invocation::ThiefOfFiveFates => "Thief of Five Fates",
+            Invocation::ThirstingBlade => "Thirsting Blade",
+            Invocation::VisionsOfDistantRealms => "Visions of Distant Realms",
+            Invocation::VoiceOfTheChainMaster => "Voice of the Chain Master",
+            Invocation::WhispersOfTheGrave => "Whispers of the Grave",
+            Invocation::WitchSight => "Witch Sight",
+        };
+        write!(f, "{}", invocation)
+    }
+}
+
+impl Character {
+    fn warlock_level(&self) -> u8 {
+        self.class_level(Class::Warlock)
+    }
+
+    pub fn pact_slot_maximum(&self) -> Option<(u8, u8)> {
+        Class::Warlock.pact_magic_slots(self.warlock_level())
+    }
+
+    /// Rebuilds the pact slots after a level change, keeping expended slots expended
+    pub fn refresh_pact_slots(&mut self) {
+        let (max, slot_level) = self.pact_slot_maximum().unwrap_or((0, 0));
+        let spent = self.pact_slots.max.saturating_sub(self.pact_slots.current);
+        self.pact_slots = PactSlots {
+            slot_level,
+            current: max.saturating_sub(spent),
+            max,
+        };
+    }
+
+    #[allow(dead_code)]
+    /// Uses a pact slot, returning how many are left
+    pub fn expend_pact_slot(&mut self) -> Result<u8, SpellcastingError> {
+        if self.pact_slots.max == 0 {
+            return Err(SpellcastingError::NotACaster);
+        }
+        if self.pact_slots.current == 0 {
+            return Err(SpellcastingError::NoSlot(self.pact_slots.slot_level));
+        }
+        self.pact_slots.current -= 1;
+        Ok(self.pact_slots.current)
+    }
+
+    /// Regains every pact slot, as on a short or long rest
+    pub fn recover_pact_slots(&mut self) -> u8 {
+        let regained = self.pact_slots.max - self.pact_slots.current;
+        self.pact_slots.current = self.pact_slots.max;
+        regained
+    }
+
+    pub fn choose_pact_boon(&mut self, boon: PactBoon) -> Result<(), PactMagicError> {
+        let level = self.warlock_level();
+        if level == 0 {
+            return Err(PactMagicError::NotAWarlock);
+        }
+        if let Some(chosen) = self.pact_boon {
+            return Err(PactMagicError::PactBoonAlreadyChosen(chosen));
+        }
+        if level < 3 {
+            return Err(PactMagicError::LevelTooLow {
+                required: 3,
+                current: level,
+            });
+        }
+
+        self.pact_boon = Some(boon);
+        Ok(())
+    }
+
+    fn check_invocation(&self, invocation: Invocation) -> Result<(), PactMagicError> {
+        let level = self.warlock_level();
+        if level == 0 {
+            return Err(PactMagicError::NotAWarlock);
+        }
+        if self.invocations.contains(&invocation) {
+            return Err(PactMagicError::AlreadyKnown(invocation));
+        }
+
+        let prerequisite = invocation.prerequisite();
+        if level < prerequisite.level.max(2) {
+            return Err(PactMagicError::LevelTooLow {
+                required: prerequisite.level.max(2),
+                current: level,
+            });
+        }
+        if let Some(pact) = prerequisite.pact
+            && self.pact_boon != Some(pact)
+        {
+            return Err(PactMagicError::WrongPact(pact));
+        }
+        if let Some(cantrip) = prerequisite.cantrip {
+            let known = self
+                .classes
+                .iter()
+                .any(|class_level| class_level.spells.cantrips.iter().any(|c| c == cantrip));
+            if !known {
+                return Err(PactMagicError::MissingCantrip(cantrip));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn learn_invocation(&mut self, invocation: Invocation) -> Result<(), PactMagicError> {
+        self.check_invocation(invocation)?;
+        let limit = Class::Warlock.invocations_known(self.warlock_level());
+        if self.invocations.len() >= limit as usize {
+            return Err(PactMagicError::NoInvocationLeft(limit));
+        }
+
+        if invocation == Invocation::BeguilingInfluence {
+            for skill in [Skill::Deception, Skill::Persuasion] {
+                if !self.skills.contains(&skill) {
+                    self.skills.push(skill);
+                }
+            }
+        }
+        self.invocations.push(invocation);
+        Ok(())
+    }
+
+    /// Invocations the character could learn right now
+    pub fn available_invocations(&self) -> Vec<Invocation> {
+        Invocation::iter()
+            .filter(|invocation| self.check_invocation(*invocation).is_ok())
+            .collect()
+    }
+
+    /// Picks a Pact Boon once available and fills the free invocations
+    pub fn choose_random_pact_features(&mut self, seed: u64) {
+        if self.warlock_level() == 0 {
+            return;
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let boons: Vec<PactBoon> = PactBoon::iter().collect();
+        if let Some(boon) = boons.choose(&mut rng) {
+            let _ = self.choose_pact_boon(*boon);
+        }
+
+        let mut invocations = self.available_invocations();
+        invocations.shuffle(&mut rng);
+        for invocation in invocations {
+            let _ = self.learn_invocation(invocation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character_of, set_levels};
+
+    fn warlock(level: u8) -> Character {
+        let mut character = character_of(Class::Warlock);
+        set_levels(&mut character, &[(Class::Warlock, level)]);
+        character.pact_boon = None;
+        character.invocations.clear();
+        character
+    }
+
+    #[test]
+    fn pact_slot_table_matches_phb() {
+        let slots = |level| Class::Warlock.pact_magic_slots(level);
+        assert_eq!(slots(1), Some((1, 1)));
+        assert_eq!(slots(2), Some((2, 1)));
+        assert_eq!(slots(3), Some((2, 2)));
+        assert_eq!(slots(9), Some((2, 5)));
+        assert_eq!(slots(11), Some((3, 5)));
+        assert_eq!(slots(17), Some((4, 5)));
+        assert_eq!(slots(20), Some((4, 5)));
+        assert_eq!(Class::Warlock.pact_magic_slots(0), None);
+        assert_eq!(Class::Wizard.pact_magic_slots(5), None);
+    }
+
+    #[test]
+    fn pact_slots_are_expended_and_recovered() {
+        let mut character = warlock(3);
+        assert_eq!(character.expend_pact_slot(), Ok(1));
+        assert_eq!(character.expend_pact_slot(), Ok(0));
+        assert_eq!(
+            character.expend_pact_slot(),
+            Err(SpellcastingError::NoSlot(2))
+        );
+        assert_eq!(character.recover_pact_slots(), 2);
+    }
+
+    #[test]
+    fn level_changes_keep_expended_pact_slots() {
+        let mut character = warlock(10);
+        character.expend_pact_slot().unwrap();
+        character.classes[0].level = 11;
+        character.refresh_pact_slots();
+        assert_eq!(character.pact_slots.max, 3);
+        assert_eq!(character.pact_slots.current, 2);
+    }
+
+    #[test]
+    fn pact_boon_waits_for_level_three() {
+        let mut character = warlock(2);
+        assert_eq!(
+            character.choose_pact_boon(PactBoon::Blade),
+            Err(PactMagicError::LevelTooLow {
+                required: 3,
+                current: 2
+            })
+        );
+        character.classes[0].level = 3;
+        assert_eq!(character.choose_pact_boon(PactBoon::Blade), Ok(()));
+        assert_eq!(
+            character.choose_pact_boon(PactBoon::Tome),
+            Err(PactMagicError::PactBoonAlreadyChosen(PactBoon::Blade))
+        );
+    }
+
+    #[test]
+    fn invocations_check_their_prerequisites() {
+        let mut character = warlock(5);
+        assert_eq!(
+            character.learn_invocation(Invocation::ThirstingBlade),
+            Err(PactMagicError::WrongPact(PactBoon::Blade))
+        );
+        character.pact_boon = Some(PactBoon::Blade);
+        assert_eq!(
+            character.learn_invocation(Invocation::ThirstingBlade),
+            Ok(())
+        );
+        assert_eq!(
+            character.learn_invocation(Invocation::Lifedrinker),
+            Err(PactMagicError::LevelTooLow {
+                required: 12,
+                current: 5
+            })
+        );
+    }
+}
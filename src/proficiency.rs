@@ -0,0 +1,239 @@
+use crate::{
+    background::Background,
+    character::Character,
+    class::Class,
+    language::Language,
+    race::Race,
+    tool::{Tool, ToolCategory},
+};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use strum::IntoEnumIterator;
+
+// Tool and language grants are lists of picks, each pick being the options
+// to choose one from. A pick with a single option is a fixed proficiency.
+
+impl Race {
+    pub fn tool_proficiencies(&self) -> Vec<Vec<Tool>> {
+        match self {
+            Race::Dwarf => vec![vec![
+                Tool::SmithsTools,
+                Tool::BrewersSupplies,
+                Tool::MasonsTools,
+            ]],
+            _ => vec![],
+        }
+    }
+
+    pub fn languages(&self) -> Vec<Vec<Language>> {
+        let race_language = match self {
+            Race::Human => Language::learnable(),
+            Race::Elf => vec![Language::Elvish],
+            Race::HalfOrc => vec![Language::Orc],
+            Race::HalfElf => vec![Language::Elvish],
+            Race::Halfling => vec![Language::Halfling],
+            Race::Gnome => vec![Language::Gnomish],
+            Race::Dwarf => vec![Language::Dwarvish],
+            Race::Dragonborn => vec![Language::Draconic],
+            Race::Tiefling => vec![Language::Infernal],
+        };
+        let mut languages = vec![vec![Language::Common], race_language];
+        if *self == Race::HalfElf {
+            languages.push(Language::learnable());
+        }
+        languages
+    }
+}
+
+impl Class {
+    pub fn tool_proficiencies(&self) -> Vec<Vec<Tool>> {
+        match self {
+            Class::Bard => vec![Tool::of_category(ToolCategory::MusicalInstrument); 3],
+            Class::Druid => vec![vec![Tool::HerbalismKit]],
+            Class::Monk => {
+                let mut options = Tool::of_category(ToolCategory::ArtisansTools);
+                options.extend(Tool::of_category(ToolCategory::MusicalInstrument));
+                vec![options]
+            }
+            Class::Rogue => vec![vec![Tool::ThievesTools]],
+            _ => vec![],
+        }
+    }
+
+    /// Tool proficiencies gained when taking the class as a later class
+    pub fn multiclass_tool_proficiency(&self) -> Vec<Vec<Tool>> {
+        match self {
+            Class::Bard => vec![Tool::of_category(ToolCategory::MusicalInstrument)],
+            Class::Rogue => vec![vec![Tool::ThievesTools]],
+            _ => vec![],
+        }
+    }
+
+    pub fn languages(&self) -> Vec<Vec<Language>> {
+        match self {
+            Class::Druid => vec![vec![Language::Druidic]],
+            Class::Rogue => vec![vec![Language::ThievesCant]],
+            _ => vec![],
+        }
+    }
+}
+
+impl Background {
+    pub fn tool_proficiencies(&self) -> Vec<Vec<Tool>> {
+        let artisans_tools = Tool::of_category(ToolCategory::ArtisansTools);
+        let gaming_set = Tool::of_category(ToolCategory::GamingSet);
+        let instrument = Tool::of_category(ToolCategory::MusicalInstrument);
+        match self {
+            Background::Acolyte | Background::Sage => vec![],
+            Background::Charlatan => vec![vec![Tool::DisguiseKit], vec![Tool::ForgeryKit]],
+            Background::Criminal => vec![gaming_set, vec![Tool::ThievesTools]],
+            Background::Entertainer => vec![vec![Tool::DisguiseKit], instrument],
+            Background::FolkHero | Background::GuildArtisan => vec![artisans_tools],
+            Background::Hermit => vec![vec![Tool::HerbalismKit]],
+            Background::Noble | Background::Soldier => vec![gaming_set],
+            Background::Outlander => vec![instrument],
+            Background::Sailor => vec![vec![Tool::NavigatorsTools]],
+            Background::Urchin => vec![vec![Tool::DisguiseKit], vec![Tool::ThievesTools]],
+        }
+    }
+
+    pub fn languages(&self) -> Vec<Vec<Language>> {
+        let count = match self {
+            Background::Acolyte | Background::Sage => 2,
+            Background::GuildArtisan
+            | Background::Hermit
+            | Background::Noble
+            | Background::Outlander => 1,
+            _ => 0,
+        };
+        vec![Language::learnable(); count]
+    }
+}
+
+// Picks one option per grant. When every option is already known the PHB
+// lets the character pick another proficiency of the same kind instead.
+fn resolve_picks<T: Copy + PartialEq>(
+    grants: Vec<Vec<T>>,
+    known: &mut Vec<T>,
+    any_other: &[T],
+    rng: &mut impl Rng,
+) {
+    for options in grants {
+        let open: Vec<T> = options.into_iter().filter(|o| !known.contains(o)).collect();
+        let pick = open.choose(rng).copied().or_else(|| {
+            let others: Vec<T> = any_other
+                .iter()
+                .copied()
+                .filter(|o| !known.contains(o))
+                .collect();
+            others.choose(rng).copied()
+        });
+        if let Some(pick) = pick {
+            known.push(pick);
+        }
+    }
+}
+
+impl Character {
+    /// Resolves the tool and language proficiencies granted by race,
+    /// starting class and background, reproducible for the same seed
+    pub fn choose_random_tools_and_languages(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let class = self.primary_class();
+
+        let mut tools: Vec<Tool> = Vec::new();
+        let tool_grants = [
+            self.race.tool_proficiencies(),
+            class.tool_proficiencies(),
+            self.background.tool_proficiencies(),
+        ]
+        .concat();
+        let all_tools: Vec<Tool> = Tool::iter().collect();
+        resolve_picks(tool_grants, &mut tools, &all_tools, &mut rng);
+
+        let mut languages: Vec<Language> = Vec::new();
+        let language_grants = [
+            self.race.languages(),
+            class.languages(),
+            self.background.languages(),
+        ]
+        .concat();
+        resolve_picks(
+            language_grants,
+            &mut languages,
+            &Language::learnable(),
+            &mut rng,
+        );
+
+        self.tools = tools;
+        self.languages = languages;
+    }
+
+    /// Adds the tool proficiencies a later class brings along
+    pub fn learn_multiclass_tools(&mut self, class: Class, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let all_tools: Vec<Tool> = Tool::iter().collect();
+        resolve_picks(
+            class.multiclass_tool_proficiency(),
+            &mut self.tools,
+            &all_tools,
+            &mut rng,
+        );
+    }
+
+    pub fn tool_check_bonus(&self, tool: Tool) -> i8 {
+        let ability_mod = self.stats.get_ability_modifier(tool.ability());
+        if self.tools.contains(&tool) {
+            ability_mod + self.proficiency_bonus()
+        } else {
+            ability_mod
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::character;
+
+    fn count<T: PartialEq>(known: &[T], item: &T) -> usize {
+        known.iter().filter(|known| *known == item).count()
+    }
+
+    #[test]
+    fn overlapping_tool_grants_are_replaced() {
+        for seed in 0..20 {
+            let mut rogue = character(Race::Human, Class::Rogue, Background::Urchin);
+            rogue.choose_random_tools_and_languages(seed);
+            // Rogue and Urchin both grant Thieves' Tools
+            assert_eq!(rogue.tools.len(), 3);
+            assert!(rogue.tools.contains(&Tool::DisguiseKit));
+            for tool in &rogue.tools {
+                assert_eq!(count(&rogue.tools, tool), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn languages_come_from_race_class_and_background() {
+        for seed in 0..20 {
+            let mut druid = character(Race::Elf, Class::Druid, Background::Sage);
+            druid.choose_random_tools_and_languages(seed);
+            assert_eq!(druid.languages.len(), 5);
+            for language in [Language::Common, Language::Elvish, Language::Druidic] {
+                assert!(druid.languages.contains(&language));
+            }
+            for language in &druid.languages {
+                assert_eq!(count(&druid.languages, language), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn tool_checks_add_proficiency_when_proficient() {
+        let mut rogue = character(Race::Human, Class::Rogue, Background::Sage);
+        rogue.tools = vec![Tool::ThievesTools];
+        assert_eq!(rogue.tool_check_bonus(Tool::ThievesTools), 2);
+        assert_eq!(rogue.tool_check_bonus(Tool::SmithsTools), 0);
+        assert!(rogue.to_string().contains("PROFICIENCIES & LANGUAGES"));
+    }
+}
@@ -1,7 +1,8 @@
+use crate::{equipment::WeaponProficiency, skill::Skill};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Formatter};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Race {
     Human,
     Elf,
@@ -14,6 +15,32 @@ pub enum Race {
     Tiefling,
 }
 
+impl Race {
+    pub fn skill_proficiencies(&self) -> Vec<Skill> {
+        match self {
+            // Keen Senses
+            Race::Elf => vec![Skill::Perception],
+            // Menacing
+            Race::HalfOrc => vec![Skill::Intimidation],
+            _ => vec![],
+        }
+    }
+
+    pub fn weapon_proficiencies(&self) -> Vec<WeaponProficiency> {
+        match self {
+            // Dwarven Combat Training
+            Race::Dwarf => {
+                WeaponProficiency::weapons(&["Battleaxe", "Handaxe", "Light Hammer", "Warhammer"])
+            }
+            // Elf Weapon Training
+            Race::Elf => {
+                WeaponProficiency::weapons(&["Longsword", "Shortsword", "Shortbow", "Longbow"])
+            }
+            _ => vec![],
+        }
+    }
+}
+
 impl fmt::Display for Race {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let race = match self {
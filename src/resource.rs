@@ -0,0 +1,333 @@
+use crate::{ability::Abilities, character::Character, class::Class};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResourceKind {
+    Rage,
+    Ki,
+    SorceryPoints,
+    ChannelDivinity,
+    BardicInspiration,
+    ActionSurge,
+    SecondWind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recovery {
+    ShortRest,
+    LongRest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcePool {
+    pub kind: ResourceKind,
+    pub current: u8,
+    pub max: u8,
+    pub recovery: Recovery,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceError {
+    NoSuchPool(ResourceKind),
+    Empty(ResourceKind),
+    NotEnough {
+        kind: ResourceKind,
+        requested: u8,
+        available: u8,
+    },
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let kind = match self {
+            ResourceKind::Rage => "Rage",
+            ResourceKind::Ki => "Ki Points",
+            ResourceKind::SorceryPoints => "Sorcery Points",
+            ResourceKind::ChannelDivinity => "Channel Divinity",
+            ResourceKind::BardicInspiration => "Bardic Inspiration",
+            ResourceKind::ActionSurge => "Action Surge",
+            ResourceKind::SecondWind => "Second Wind",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Recovery::ShortRest => write!(f, "Short Rest"),
+            Recovery::LongRest => write!(f, "Long Rest"),
+        }
+    }
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ResourceError::NoSuchPool(kind) => write!(f, "Character has no {} pool", kind),
+            ResourceError::Empty(kind) => write!(f, "No {} left", kind),
+            ResourceError::NotEnough {
+                kind,
+                requested,
+                available,
+            } => write!(
+                f,
+                "Not enough {}: requested {}, {} available",
+                kind, requested, available
+            ),
+        }
+    }
+}
+
+impl ResourcePool {
+    pub fn new(kind: ResourceKind, max: u8, recovery: Recovery) -> Self {
+        ResourcePool {
+            kind,
+            current: max,
+            max,
+            recovery,
+        }
+    }
+
+    /// Barbarians rage without limit at 20th level
+    pub fn is_unlimited(&self) -> bool {
+        self.max == u8::MAX
+    }
+
+    pub fn spend(&mut self, amount: u8) -> Result<u8, ResourceError> {
+        if self.is_unlimited() {
+            return Ok(self.current);
+        }
+        if self.current == 0 {
+            return Err(ResourceError::Empty(self.kind));
+        }
+        if amount > self.current {
+            return Err(ResourceError::NotEnough {
+                kind: self.kind,
+                requested: amount,
+                available: self.current,
+            });
+        }
+        self.current -= amount;
+        Ok(self.current)
+    }
+
+    /// Restores up to `amount`, returning how much was actually regained
+    pub fn restore(&mut self, amount: u8) -> u8 {
+        let regained = amount.min(self.max - self.current);
+        self.current += regained;
+        regained
+    }
+
+    pub fn recover(&mut self) -> u8 {
+        self.restore(self.max)
+    }
+}
+
+impl Character {
+    /// Class resources with their maximum at the current class levels
+    pub fn resource_maximums(&self) -> Vec<ResourcePool> {
+        let mut pools: Vec<ResourcePool> = Vec::new();
+
+        for class_level in &self.classes {
+            for pool in self.class_resources(class_level.class, class_level.level) {
+                // Pools shared by two classes (Channel Divinity) don't stack
+                match pools.iter_mut().find(|known| known.kind == pool.kind) {
+                    Some(known) if known.max < pool.max => *known = pool,
+                    Some(_) => {}
+                    None => pools.push(pool),
+                }
+            }
+        }
+
+        pools
+    }
+
+    fn class_resources(&self, class: Class, level: u8) -> Vec<ResourcePool> {
+        let mut pools = Vec::new();
+
+        if let Some(uses) = class.rage_uses(level) {
+            pools.push(ResourcePool::new(
+                ResourceKind::Rage,
+                uses,
+                Recovery::LongRest,
+            ));
+        }
+        if let Some(points) = class.ki_points(level) {
+            pools.push(ResourcePool::new(
+                ResourceKind::Ki,
+                points,
+                Recovery::ShortRest,
+            ));
+        }
+        if let Some(points) = class.sorcery_points(level) {
+            pools.push(ResourcePool::new(
+                ResourceKind::SorceryPoints,
+                points,
+                Recovery::LongRest,
+            ));
+        }
+        if let Some(uses) = class.channel_divinity_uses(level) {
+            pools.push(ResourcePool::new(
+                ResourceKind::ChannelDivinity,
+                uses,
+                Recovery::ShortRest,
+            ));
+        }
+        if class == Class::Bard {
+            let uses = self.stats.get_ability_modifier(Abilities::Charisma).max(1) as u8;
+            // Font of Inspiration
+            let recovery = if level >= 5 {
+                Recovery::ShortRest
+            } else {
+                Recovery::LongRest
+            };
+            pools.push(ResourcePool::new(
+                ResourceKind::BardicInspiration,
+                uses,
+                recovery,
+            ));
+        }
+        if let Some(uses) = class.action_surge_uses(level) {
+            pools.push(ResourcePool::new(
+                ResourceKind::ActionSurge,
+                uses,
+                Recovery::ShortRest,
+            ));
+        }
+        if class == Class::Fighter {
+            pools.push(ResourcePool::new(
+                ResourceKind::SecondWind,
+                1,
+                Recovery::ShortRest,
+            ));
+        }
+
+        pools
+    }
+
+    /// Rebuilds the pools after level or ability changes, keeping spent uses spent
+    pub fn refresh_resources(&mut self) {
+        let mut pools = self.resource_maximums();
+        for pool in pools.iter_mut() {
+            if let Some(old) = self.resources.iter().find(|old| old.kind == pool.kind) {
+                let spent = old.max.saturating_sub(old.current);
+                pool.current = pool.max.saturating_sub(spent);
+            }
+        }
+        self.resources = pools;
+    }
+
+    #[allow(dead_code)]
+    pub fn resource(&self, kind: ResourceKind) -> Option<&ResourcePool> {
+        self.resources.iter().find(|pool| pool.kind == kind)
+    }
+
+    fn resource_mut(&mut self, kind: ResourceKind) -> Result<&mut ResourcePool, ResourceError> {
+        self.resources
+            .iter_mut()
+            .find(|pool| pool.kind == kind)
+            .ok_or(ResourceError::NoSuchPool(kind))
+    }
+
+    #[allow(dead_code)]
+    /// Spends from a pool, returning what is left
+    pub fn spend_resource(&mut self, kind: ResourceKind, amount: u8) -> Result<u8, ResourceError> {
+        self.resource_mut(kind)?.spend(amount)
+    }
+
+    #[allow(dead_code)]
+    /// Restores to a pool, returning how much was regained
+    pub fn restore_resource(
+        &mut self,
+        kind: ResourceKind,
+        amount: u8,
+    ) -> Result<u8, ResourceError> {
+        Ok(self.resource_mut(kind)?.restore(amount))
+    }
+
+    /// Recovers every pool that comes back on the given rest. A long rest
+    /// also recovers the short rest pools.
+    pub fn recover_resources(&mut self, rest: Recovery) -> Vec<(ResourceKind, u8)> {
+        self.resources
+            .iter_mut()
+            .filter(|pool| rest == Recovery::LongRest || pool.recovery == rest)
+            .map(|pool| (pool.kind, pool.recover()))
+            .filter(|(_, regained)| *regained > 0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character_of, set_abilities, set_levels};
+
+    fn barbarian_fighter() -> Character {
+        let mut character = character_of(Class::Barbarian);
+        set_levels(
+            &mut character,
+            &[(Class::Barbarian, 3), (Class::Fighter, 2)],
+        );
+        character
+    }
+
+    #[test]
+    fn pools_follow_class_levels_and_abilities() {
+        let character = barbarian_fighter();
+        let max = |kind| character.resource(kind).map(|pool| pool.max);
+        assert_eq!(max(ResourceKind::Rage), Some(3));
+        assert_eq!(max(ResourceKind::ActionSurge), Some(1));
+        assert_eq!(max(ResourceKind::SecondWind), Some(1));
+        assert_eq!(max(ResourceKind::Ki), None);
+
+        let mut bard = character_of(Class::Bard);
+        set_abilities(&mut bard, 16);
+        bard.refresh_resources();
+        let inspiration = bard.resource(ResourceKind::BardicInspiration).unwrap();
+        assert_eq!(inspiration.max, 3);
+        assert_eq!(inspiration.recovery, Recovery::LongRest);
+    }
+
+    #[test]
+    fn spending_an_empty_pool_fails() {
+        let mut character = barbarian_fighter();
+        assert_eq!(character.spend_resource(ResourceKind::Rage, 2), Ok(1));
+        assert_eq!(
+            character.spend_resource(ResourceKind::Rage, 2),
+            Err(ResourceError::NotEnough {
+                kind: ResourceKind::Rage,
+                requested: 2,
+                available: 1
+            })
+        );
+        assert_eq!(character.spend_resource(ResourceKind::Rage, 1), Ok(0));
+        assert_eq!(
+            character.spend_resource(ResourceKind::Rage, 1),
+            Err(ResourceError::Empty(ResourceKind::Rage))
+        );
+        assert_eq!(
+            character.spend_resource(ResourceKind::Ki, 1),
+            Err(ResourceError::NoSuchPool(ResourceKind::Ki))
+        );
+        assert_eq!(character.restore_resource(ResourceKind::Rage, 5), Ok(3));
+    }
+
+    #[test]
+    fn rests_recover_their_own_pools() {
+        let mut character = barbarian_fighter();
+        character.spend_resource(ResourceKind::Rage, 1).unwrap();
+        character
+            .spend_resource(ResourceKind::ActionSurge, 1)
+            .unwrap();
+
+        let summary = character.short_rest(0).unwrap();
+        assert_eq!(summary.resources, vec![(ResourceKind::ActionSurge, 1)]);
+        assert_eq!(character.resource(ResourceKind::Rage).unwrap().current, 2);
+
+        let summary = character.long_rest().unwrap();
+        assert_eq!(summary.resources, vec![(ResourceKind::Rage, 1)]);
+        assert_eq!(character.resource(ResourceKind::Rage).unwrap().current, 3);
+    }
+}
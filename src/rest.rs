@@ -0,0 +1,313 @@
+use crate::{
+    ability::Abilities,
+    character::Character,
+    death_save::LifeState,
+    dice::Dice,
+    resource::{Recovery, ResourceKind},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+/// Hit dice of one die type, classes sharing a die share the pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HitDicePool {
+    pub sides: u8,
+    pub current: u8,
+    pub max: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestError {
+    NotEnoughHitDice {
+        requested: u8,
+        available: u8,
+    },
+    /// Resting needs a living character, a long rest at least 1 HP
+    CantRest(LifeState),
+}
+
+/// What a short rest gave back
+#[derive(Debug, Clone, Default)]
+pub struct ShortRestSummary {
+    /// Die sides and the roll of every hit die spent
+    pub hit_dice_rolls: Vec<(u8, u8)>,
+    pub hp_regained: u16,
+    pub resources: Vec<(ResourceKind, u8)>,
+    pub pact_slots: u8,
+}
+
+/// What a long rest gave back
+#[derive(Debug, Clone, Default)]
+pub struct LongRestSummary {
+    pub hp_regained: u16,
+    pub hit_dice_regained: u8,
+    pub spell_slots: u8,
+    pub pact_slots: u8,
+    pub resources: Vec<(ResourceKind, u8)>,
+}
+
+impl fmt::Display for HitDicePool {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{} d{}", self.current, self.max, self.sides)
+    }
+}
+
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RestError::NotEnoughHitDice {
+                requested,
+                available,
+            } => write!(
+                f,
+                "Not enough hit dice: requested {}, {} available",
+                requested, available
+            ),
+            RestError::CantRest(state) => {
+                write!(f, "Character is {} and can't benefit from a rest", state)
+            }
+        }
+    }
+}
+
+fn write_resources(f: &mut Formatter, resources: &[(ResourceKind, u8)]) -> fmt::Result {
+    for (kind, regained) in resources {
+        write!(f, ", {} {}", regained, kind)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for ShortRestSummary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Short rest: +{} HP", self.hp_regained)?;
+        if !self.hit_dice_rolls.is_empty() {
+            let rolls: Vec<String> = self
+                .hit_dice_rolls
+                .iter()
+                .map(|(sides, roll)| format!("d{} {}", sides, roll))
+                .collect();
+            write!(f, " ({})", rolls.join(", "))?;
+        }
+        write_resources(f, &self.resources)?;
+        if self.pact_slots > 0 {
+            write!(f, ", {} pact slot(s)", self.pact_slots)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LongRestSummary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Long rest: +{} HP, {} hit dice",
+            self.hp_regained, self.hit_dice_regained
+        )?;
+        if self.spell_slots > 0 {
+            write!(f, ", {} spell slot(s)", self.spell_slots)?;
+        }
+        if self.pact_slots > 0 {
+            write!(f, ", {} pact slot(s)", self.pact_slots)?;
+        }
+        write_resources(f, &self.resources)
+    }
+}
+
+impl Character {
+    /// One hit die per class level, grouped by die type, largest first
+    pub fn hit_dice_maximums(&self) -> Vec<HitDicePool> {
+        let mut pools: Vec<HitDicePool> = Vec::new();
+        for class_level in &self.classes {
+            let sides = class_level.class.get_class_hit_dice().dice_sides;
+            match pools.iter_mut().find(|pool| pool.sides == sides) {
+                Some(pool) => pool.max += class_level.level,
+                None => pools.push(HitDicePool {
+                    sides,
+                    current: 0,
+                    max: class_level.level,
+                }),
+            }
+        }
+        pools.sort_by_key(|pool| std::cmp::Reverse(pool.sides));
+        pools
+    }
+
+    /// Rebuilds the hit dice after a level change, keeping spent dice spent
+    pub fn refresh_hit_dice(&mut self) {
+        let mut pools = self.hit_dice_maximums();
+        for pool in pools.iter_mut() {
+            let spent = self
+                .hit_dice
+                .iter()
+                .find(|old| old.sides == pool.sides)
+                .map_or(0, |old| old.max.saturating_sub(old.current));
+            pool.current = pool.max.saturating_sub(spent);
+        }
+        self.hit_dice = pools;
+    }
+
+    pub fn hit_dice_remaining(&self) -> u8 {
+        self.hit_dice.iter().map(|pool| pool.current).sum()
+    }
+
+    #[allow(dead_code)]
+    /// Spends `hit_dice` dice, largest first, each healing its roll plus the
+    /// CON modifier. Short rest resources and pact slots come back.
+    pub fn short_rest(&mut self, hit_dice: u8) -> Result<ShortRestSummary, RestError> {
+        if matches!(self.life_state, LifeState::Dead | LifeState::Dying) {
+            return Err(RestError::CantRest(self.life_state));
+        }
+        let available = self.hit_dice_remaining();
+        if hit_dice > available {
+            return Err(RestError::NotEnoughHitDice {
+                requested: hit_dice,
+                available,
+            });
+        }
+
+        let modifier = self.stats.get_ability_modifier(Abilities::Constitution) as i16;
+        let mut summary = ShortRestSummary::default();
+        for _ in 0..hit_dice {
+            let Some(pool) = self.hit_dice.iter_mut().find(|pool| pool.current > 0) else {
+                break;
+            };
+            pool.current -= 1;
+            let roll = Dice::new(1, pool.sides, 0).roll();
+            summary.hit_dice_rolls.push((pool.sides, roll));
+            summary.hp_regained += self.heal((roll as i16 + modifier).max(0) as u16);
+        }
+        summary.resources = self.recover_resources(Recovery::ShortRest);
+        summary.pact_slots = self.recover_pact_slots();
+        Ok(summary)
+    }
+
+    #[allow(dead_code)]
+    /// Restores all HP, slots and resources, and half the total hit dice
+    /// (at least one), largest dice first. A character at 0 HP gets nothing.
+    pub fn long_rest(&mut self) -> Result<LongRestSummary, RestError> {
+        if self.current_hp == 0 || self.life_state != LifeState::Alive {
+            return Err(RestError::CantRest(self.life_state));
+        }
+        let mut summary = LongRestSummary {
+            hp_regained: self.heal(self.max_hp),
+            ..LongRestSummary::default()
+        };
+
+        let total: u8 = self.hit_dice.iter().map(|pool| pool.max).sum();
+        let mut to_regain = (total / 2).max(1);
+        for pool in self.hit_dice.iter_mut() {
+            let regained = to_regain.min(pool.max - pool.current);
+            pool.current += regained;
+            to_regain -= regained;
+            summary.hit_dice_regained += regained;
+        }
+
+        summary.spell_slots = self.recover_spell_slots();
+        summary.pact_slots = self.recover_pact_slots();
+        summary.resources = self.recover_resources(Recovery::LongRest);
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::Class,
+        equipment::DamageType,
+        test_support::{character_of, set_levels},
+    };
+
+    fn fighter_wizard() -> Character {
+        let mut character = character_of(Class::Fighter);
+        set_levels(&mut character, &[(Class::Fighter, 3), (Class::Wizard, 2)]);
+        character.max_hp = 30;
+        character.current_hp = 30;
+        character
+    }
+
+    #[test]
+    fn hit_dice_are_pooled_by_die_largest_first() {
+        let character = fighter_wizard();
+        assert_eq!(
+            character.hit_dice,
+            vec![
+                HitDicePool {
+                    sides: 10,
+                    current: 3,
+                    max: 3
+                },
+                HitDicePool {
+                    sides: 6,
+                    current: 2,
+                    max: 2
+                },
+            ]
+        );
+        assert_eq!(character.hit_dice_remaining(), 5);
+    }
+
+    #[test]
+    fn short_rest_spends_largest_dice_first() {
+        let mut character = fighter_wizard();
+        character.take_damage(20, DamageType::Slashing);
+        let summary = character.short_rest(4).unwrap();
+        let sides: Vec<u8> = summary
+            .hit_dice_rolls
+            .iter()
+            .map(|(sides, _)| *sides)
+            .collect();
+        assert_eq!(sides, vec![10, 10, 10, 6]);
+        assert_eq!(character.hit_dice_remaining(), 1);
+        assert_eq!(character.current_hp, 10 + summary.hp_regained);
+        assert_eq!(
+            character.short_rest(2).unwrap_err(),
+            RestError::NotEnoughHitDice {
+                requested: 2,
+                available: 1
+            }
+        );
+    }
+
+    #[test]
+    fn no_short_rest_while_dying_or_dead() {
+        let mut character = fighter_wizard();
+        character.take_damage(30, DamageType::Slashing);
+        assert_eq!(
+            character.short_rest(1).unwrap_err(),
+            RestError::CantRest(LifeState::Dying)
+        );
+        character.fail_death_saves(3);
+        assert_eq!(
+            character.short_rest(1).unwrap_err(),
+            RestError::CantRest(LifeState::Dead)
+        );
+        assert_eq!(character.hit_dice_remaining(), 5);
+    }
+
+    #[test]
+    fn no_long_rest_at_zero_hp() {
+        let mut character = fighter_wizard();
+        character.take_damage(30, DamageType::Slashing);
+        character.stabilize(20).unwrap();
+        assert_eq!(
+            character.long_rest().unwrap_err(),
+            RestError::CantRest(LifeState::Stable)
+        );
+        assert_eq!(character.current_hp, 0);
+    }
+
+    #[test]
+    fn long_rest_restores_hp_and_half_the_hit_dice() {
+        let mut character = fighter_wizard();
+        character.take_damage(25, DamageType::Slashing);
+        for pool in character.hit_dice.iter_mut() {
+            pool.current = 0;
+        }
+        let summary = character.long_rest().unwrap();
+        assert_eq!(summary.hp_regained, 25);
+        assert_eq!(character.current_hp, 30);
+        assert_eq!(summary.hit_dice_regained, 2);
+        assert_eq!(character.hit_dice[0].current, 2);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{ability::Abilities, character::Character};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Formatter};
+use strum_macros::EnumIter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
 pub enum Skill {
     // Strength
     Athletics,
@@ -99,3 +100,27 @@ impl fmt::Display for Skill {
         write!(f, "{}", skill)
     }
 }
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillChoiceError {
+    WrongCount { expected: usize, got: usize },
+    NotAllowed(Skill),
+    Duplicate(Skill),
+}
+
+impl fmt::Display for SkillChoiceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SkillChoiceError::WrongCount { expected, got } => {
+                write!(f, "Expected {} skills, got {}", expected, got)
+            }
+            SkillChoiceError::NotAllowed(skill) => {
+                write!(f, "{} is not on the class skill list", skill)
+            }
+            SkillChoiceError::Duplicate(skill) => {
+                write!(f, "{} is already proficient, pick another skill", skill)
+            }
+        }
+    }
+}
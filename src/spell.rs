@@ -0,0 +1,314 @@
+use crate::{ability::Abilities, class::Class, dice::Dice, equipment::DamageType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Formatter};
+use std::sync::OnceLock;
+use std::{fs, io};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spell {
+    pub name: String,
+    /// 0 for cantrips
+    pub level: u8,
+    pub school: SpellSchool,
+    pub casting_time: String,
+    pub range: String,
+    pub components: Vec<Component>,
+    /// Material component, required when `components` lists M
+    pub material: Option<String>,
+    pub duration: String,
+    pub concentration: bool,
+    pub ritual: bool,
+    pub classes: Vec<Class>,
+    pub damage: Option<String>,
+    pub damage_type: Option<DamageType>,
+    /// Hit points restored, the spellcasting modifier is added on top
+    pub healing: Option<String>,
+    /// Dice added to the damage or healing for each slot level above the spell's
+    pub upcast_dice: Option<String>,
+    #[serde(default)]
+    pub attack_roll: bool,
+    /// Ability the target saves with
+    pub save: Option<Abilities>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpellSchool {
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Component {
+    #[serde(rename = "V")]
+    Verbal,
+    #[serde(rename = "S")]
+    Somatic,
+    #[serde(rename = "M")]
+    Material,
+}
+
+/// data/spells.toml is read once, the first time a spell is looked up
+static SPELL_DATABASE: OnceLock<Result<HashMap<String, Spell>, String>> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+struct SpellDatabase {
+    spell: Vec<Spell>,
+}
+
+/// Filters for searching the spell database, unset filters match everything
+#[derive(Debug, Clone, Default)]
+pub struct SpellQuery {
+    pub class: Option<Class>,
+    pub level: Option<u8>,
+    pub school: Option<SpellSchool>,
+    pub text: Option<String>,
+}
+
+impl fmt::Display for SpellSchool {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let school = match self {
+            SpellSchool::Abjuration => "Abjuration",
+            SpellSchool::Conjuration => "Conjuration",
+            SpellSchool::Divination => "Divination",
+            SpellSchool::Enchantment => "Enchantment",
+            SpellSchool::Evocation => "Evocation",
+            SpellSchool::Illusion => "Illusion",
+            SpellSchool::Necromancy => "Necromancy",
+            SpellSchool::Transmutation => "Transmutation",
+        };
+        write!(f, "{}", school)
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Component::Verbal => write!(f, "V"),
+            Component::Somatic => write!(f, "S"),
+            Component::Material => write!(f, "M"),
+        }
+    }
+}
+
+impl Spell {
+    pub fn load_spell_database() -> Result<HashMap<String, Spell>, io::Error> {
+        let toml_content = fs::read_to_string("data/spells.toml")?;
+
+        let database: SpellDatabase = toml::from_str(&toml_content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("data/spells.toml: {}", e),
+            )
+        })?;
+
+        let mut spell_map: HashMap<String, Spell> = HashMap::new();
+        for spell in database.spell {
+            spell
+                .validate()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            if spell_map.contains_key(&spell.name) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("data/spells.toml: spell '{}' is listed twice", spell.name),
+                ));
+            }
+            spell_map.insert(spell.name.clone(), spell);
+        }
+
+        Ok(spell_map)
+    }
+
+    /// The shared spell database, loaded on first use. A load error is
+    /// kept and returned on every later call.
+    pub fn database() -> Result<&'static HashMap<String, Spell>, io::Error> {
+        SPELL_DATABASE
+            .get_or_init(|| Spell::load_spell_database().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.clone()))
+    }
+
+    #[allow(dead_code)]
+    pub fn get_spell(key: &str) -> Result<Option<Spell>, io::Error> {
+        Ok(Spell::database()?.get(key).cloned())
+    }
+
+    /// Checks what the TOML types can't express
+    fn validate(&self) -> Result<(), String> {
+        let fail = |reason: String| {
+            Err(format!(
+                "data/spells.toml: spell '{}' {}",
+                self.name, reason
+            ))
+        };
+
+        if self.level > 9 {
+            return fail(format!("has level {}, expected 0 to 9", self.level));
+        }
+        if self.classes.is_empty() {
+            return fail("has no classes".to_string());
+        }
+        if self.components.is_empty() {
+            return fail("has no components".to_string());
+        }
+        let unique: HashSet<Component> = self.components.iter().copied().collect();
+        if unique.len() != self.components.len() {
+            return fail("lists a component twice".to_string());
+        }
+        match (
+            self.components.contains(&Component::Material),
+            &self.material,
+        ) {
+            (true, None) => return fail("has an M component but no material".to_string()),
+            (false, Some(_)) => return fail("has a material but no M component".to_string()),
+            _ => {}
+        }
+        if let Some(damage) = &self.damage {
+            if Dice::from_notation(damage).is_none() {
+                return fail(format!("has damage '{}', expected dice like 2d6", damage));
+            }
+            if self.damage_type.is_none() {
+                return fail("has damage but no damage_type".to_string());
+            }
+        } else if self.damage_type.is_some() {
+            return fail("has a damage_type but no damage".to_string());
+        }
+        for (field, dice) in [
+            ("healing", &self.healing),
+            ("upcast_dice", &self.upcast_dice),
+        ] {
+            if let Some(dice) = dice
+                && Dice::from_notation(dice).is_none()
+            {
+                return fail(format!("has {} '{}', expected dice like 2d6", field, dice));
+            }
+        }
+        if self.upcast_dice.is_some() && self.damage.is_none() && self.healing.is_none() {
+            return fail("has upcast_dice but no damage or healing".to_string());
+        }
+        if self.upcast_dice.is_some() && self.is_cantrip() {
+            return fail("is a cantrip and can't be upcast".to_string());
+        }
+        if self.attack_roll && self.save.is_some() {
+            return fail("has both attack_roll and save".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn is_cantrip(&self) -> bool {
+        self.level == 0
+    }
+
+    pub fn damage_dice(&self) -> Option<Dice> {
+        self.damage.as_deref().and_then(Dice::from_notation)
+    }
+
+    pub fn healing_dice(&self) -> Option<Dice> {
+        self.healing.as_deref().and_then(Dice::from_notation)
+    }
+
+    pub fn upcast_dice(&self) -> Option<Dice> {
+        self.upcast_dice.as_deref().and_then(Dice::from_notation)
+    }
+
+    #[allow(dead_code)]
+    /// "V, S, M (material)" as written in the PHB
+    pub fn components_text(&self) -> String {
+        let components: Vec<String> = self.components.iter().map(|c| c.to_string()).collect();
+        match &self.material {
+            Some(material) => format!("{} ({})", components.join(", "), material),
+            None => components.join(", "),
+        }
+    }
+}
+
+impl SpellQuery {
+    pub fn class(mut self, class: Class) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    pub fn level(mut self, level: u8) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn school(mut self, school: SpellSchool) -> Self {
+        self.school = Some(school);
+        self
+    }
+
+    #[allow(dead_code)]
+    /// Case-insensitive search in the spell's name and description
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_lowercase());
+        self
+    }
+
+    pub fn matches(&self, spell: &Spell) -> bool {
+        self.class
+            .is_none_or(|class| spell.classes.contains(&class))
+            && self.level.is_none_or(|level| spell.level == level)
+            && self.school.is_none_or(|school| spell.school == school)
+            && self.text.as_ref().is_none_or(|text| {
+                spell.name.to_lowercase().contains(text)
+                    || spell.description.to_lowercase().contains(text)
+            })
+    }
+
+    /// Matching spells sorted by level, then by name
+    pub fn run<'a>(&self, spells: &'a HashMap<String, Spell>) -> Vec<&'a Spell> {
+        let mut found: Vec<&Spell> = spells
+            .values()
+            .filter(|spell| self.matches(spell))
+            .collect();
+        found.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.name.cmp(&b.name)));
+        found
+    }
+}
+
+impl fmt::Display for Spell {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let level = match self.level {
+            0 => format!("{} cantrip", self.school),
+            1 => format!("1st-level {}", self.school.to_string().to_lowercase()),
+            2 => format!("2nd-level {}", self.school.to_string().to_lowercase()),
+            3 => format!("3rd-level {}", self.school.to_string().to_lowercase()),
+            n => format!("{}th-level {}", n, self.school.to_string().to_lowercase()),
+        };
+        write!(f, "{} ({}", self.name, level)?;
+        if self.ritual {
+            write!(f, ", ritual")?;
+        }
+        if self.concentration {
+            write!(f, ", concentration")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_component_fails_validation() {
+        let mut spell = Spell::get_spell("Fire Bolt").unwrap().unwrap();
+        assert_eq!(spell.validate(), Ok(()));
+
+        spell.components.push(spell.components[0]);
+        assert_eq!(
+            spell.validate(),
+            Err("data/spells.toml: spell 'Fire Bolt' lists a component twice".to_string())
+        );
+    }
+}
@@ -0,0 +1,638 @@
+use crate::{
+    ability::Abilities,
+    character::Character,
+    class::Class,
+    multiclass::ClassLevel,
+    spell::{Spell, SpellQuery},
+    subclass::Subclass,
+};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Formatter};
+
+/// How a class decides which spells it can cast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellListModel {
+    /// A fixed number of spells known, swapped one at a time on level up
+    Known,
+    /// Prepares each day from the whole class list
+    Prepared,
+    /// Copies spells into a spellbook and prepares from it
+    Spellbook,
+}
+
+/// Spells a character has from one class
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpellList {
+    pub cantrips: Vec<String>,
+    pub known: Vec<String>,
+    pub spellbook: Vec<String>,
+    pub prepared: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpellListError {
+    NotACaster(Class),
+    WrongModel(Class),
+    NoSuchSpell(String),
+    NotOnClassList {
+        spell: String,
+        class: Class,
+    },
+    LevelTooHigh {
+        spell: String,
+        max_level: u8,
+    },
+    NotACantrip(String),
+    IsACantrip(String),
+    AlreadyKnown(String),
+    NotKnown(String),
+    NotInSpellbook(String),
+    CantripLimit(u8),
+    KnownLimit(u8),
+    PreparedLimit(u8),
+    /// data/spells.toml couldn't be loaded
+    Database(String),
+}
+
+impl fmt::Display for SpellListError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SpellListError::NotACaster(class) => write!(f, "{} can't cast spells yet", class),
+            SpellListError::WrongModel(class) => {
+                write!(f, "{} doesn't manage spells that way", class)
+            }
+            SpellListError::NoSuchSpell(spell) => write!(f, "Unknown spell: {}", spell),
+            SpellListError::NotOnClassList { spell, class } => {
+                write!(f, "{} is not on the {} spell list", spell, class)
+            }
+            SpellListError::LevelTooHigh { spell, max_level } => {
+                write!(
+                    f,
+                    "{} is above the highest spell level {}",
+                    spell, max_level
+                )
+            }
+            SpellListError::NotACantrip(spell) => write!(f, "{} is not a cantrip", spell),
+            SpellListError::IsACantrip(spell) => write!(f, "{} is a cantrip", spell),
+            SpellListError::AlreadyKnown(spell) => write!(f, "{} is already known", spell),
+            SpellListError::NotKnown(spell) => write!(f, "{} is not known", spell),
+            SpellListError::NotInSpellbook(spell) => {
+                write!(f, "{} is not in the spellbook", spell)
+            }
+            SpellListError::CantripLimit(limit) => write!(f, "Already knows {} cantrips", limit),
+            SpellListError::KnownLimit(limit) => write!(f, "Already knows {} spells", limit),
+            SpellListError::PreparedLimit(limit) => {
+                write!(f, "Already has {} spells prepared", limit)
+            }
+            SpellListError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Class {
+    pub fn spell_list_model(&self) -> Option<SpellListModel> {
+        match self {
+            Class::Bard | Class::Ranger | Class::Sorcerer | Class::Warlock => {
+                Some(SpellListModel::Known)
+            }
+            Class::Cleric | Class::Druid | Class::Paladin => Some(SpellListModel::Prepared),
+            Class::Wizard => Some(SpellListModel::Spellbook),
+            Class::Barbarian | Class::Fighter | Class::Monk | Class::Rogue => None,
+        }
+    }
+
+    pub fn cantrips_known(&self, level: u8) -> u8 {
+        let base = match self {
+            Class::Bard | Class::Druid | Class::Warlock => 2,
+            Class::Cleric | Class::Wizard => 3,
+            Class::Sorcerer => 4,
+            _ => return 0,
+        };
+        match level {
+            0..=3 => base,
+            4..=9 => base + 1,
+            _ => base + 2,
+        }
+    }
+
+    /// PHB Spells Known column, `None` for classes that prepare spells
+    pub fn spells_known(&self, level: u8) -> Option<u8> {
+        let table: [u8; 20] = match self {
+            Class::Bard => [
+                4, 5, 6, 7, 8, 9, 10, 11, 12, 14, 15, 15, 16, 18, 19, 19, 20, 22, 22, 22,
+            ],
+            Class::Ranger => [
+                0, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11,
+            ],
+            Class::Sorcerer => [
+                2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 12, 13, 13, 14, 14, 15, 15, 15, 15,
+            ],
+            Class::Warlock => [
+                2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 11, 11, 12, 12, 13, 13, 14, 14, 15, 15,
+            ],
+            _ => return None,
+        };
+        Some(table[level.clamp(1, 20) as usize - 1])
+    }
+}
+
+impl Subclass {
+    /// Eldritch Knights and Arcane Tricksters learn from the wizard list
+    fn spells_known(&self, level: u8) -> Option<u8> {
+        if self.spellcasting_ability().is_none() || level < 3 {
+            return None;
+        }
+        Some(match level {
+            3 => 3,
+            4..=6 => 4,
+            7 => 5,
+            8..=9 => 6,
+            10 => 7,
+            11..=12 => 8,
+            13 => 9,
+            14..=15 => 10,
+            16..=18 => 11,
+            19 => 12,
+            _ => 13,
+        })
+    }
+
+    fn cantrips_known(&self, level: u8) -> u8 {
+        let base = match self {
+            Subclass::EldritchKnight => 2,
+            Subclass::ArcaneTrickster => 3,
+            _ => return 0,
+        };
+        match level {
+            0..=2 => 0,
+            3..=9 => base,
+            _ => base + 1,
+        }
+    }
+}
+
+impl ClassLevel {
+    pub fn spell_list_model(&self) -> Option<SpellListModel> {
+        self.class.spell_list_model().or_else(|| {
+            self.subclass
+                .and_then(|subclass| subclass.spellcasting_ability())
+                .map(|_| SpellListModel::Known)
+        })
+    }
+
+    /// Class whose spell list the spells are learned from
+    pub fn spell_list_class(&self) -> Class {
+        match self.subclass {
+            Some(subclass) if subclass.spellcasting_ability().is_some() => Class::Wizard,
+            _ => self.class,
+        }
+    }
+
+    pub fn cantrip_limit(&self) -> u8 {
+        match self.subclass {
+            Some(subclass) if self.class.spell_list_model().is_none() => {
+                subclass.cantrips_known(self.level)
+            }
+            _ => self.class.cantrips_known(self.level),
+        }
+    }
+
+    pub fn known_limit(&self) -> Option<u8> {
+        self.class.spells_known(self.level).or_else(|| {
+            self.subclass
+                .and_then(|subclass| subclass.spells_known(self.level))
+        })
+    }
+
+    /// Spells a wizard writes into the spellbook for free: six at 1st
+    /// level and two more every level
+    pub fn free_spellbook_spells(&self) -> u8 {
+        6 + 2 * (self.level - 1)
+    }
+}
+
+impl Character {
+    fn class_level_for(&self, class: Class) -> Result<&ClassLevel, SpellListError> {
+        self.classes
+            .iter()
+            .find(|class_level| class_level.class == class)
+            .filter(|class_level| class_level.spell_list_model().is_some())
+            .ok_or(SpellListError::NotACaster(class))
+    }
+
+    fn spell_list_mut(&mut self, class: Class) -> &mut SpellList {
+        &mut self
+            .classes
+            .iter_mut()
+            .find(|class_level| class_level.class == class)
+            .expect("class checked by class_level_for")
+            .spells
+    }
+
+    /// Number of spells a preparing class can have ready after a long rest
+    pub fn prepared_limit(&self, class: Class) -> Result<u8, SpellListError> {
+        let class_level = self.class_level_for(class)?;
+        if class_level.max_spell_level() == 0 {
+            return Ok(0);
+        }
+        let ability = class
+            .spellcasting_ability()
+            .unwrap_or(Abilities::Intelligence);
+        let modifier = self.stats.get_ability_modifier(ability);
+        let levels = match class {
+            Class::Paladin => class_level.level / 2,
+            _ => class_level.level,
+        };
+        Ok((modifier + levels as i8).max(1) as u8)
+    }
+
+    /// Looks the spell up and checks it against the class list and spell level
+    fn check_spell(
+        &self,
+        class: Class,
+        name: &str,
+        spells: &HashMap<String, Spell>,
+    ) -> Result<Spell, SpellListError> {
+        let class_level = self.class_level_for(class)?;
+        let spell = spells
+            .get(name)
+            .ok_or_else(|| SpellListError::NoSuchSpell(name.to_string()))?;
+        let list_class = class_level.spell_list_class();
+        if !spell.classes.contains(&list_class) {
+            return Err(SpellListError::NotOnClassList {
+                spell: name.to_string(),
+                class: list_class,
+            });
+        }
+        let max_level = class_level.max_spell_level();
+        if spell.level > max_level {
+            return Err(SpellListError::LevelTooHigh {
+                spell: name.to_string(),
+                max_level,
+            });
+        }
+        Ok(spell.clone())
+    }
+
+    pub fn learn_cantrip(&mut self, class: Class, name: &str) -> Result<(), SpellListError> {
+        let spells = Spell::database().map_err(|e| SpellListError::Database(e.to_string()))?;
+        let spell = self.check_spell(class, name, spells)?;
+        if !spell.is_cantrip() {
+            return Err(SpellListError::NotACantrip(name.to_string()));
+        }
+        let class_level = self.class_level_for(class)?;
+        if class_level
+            .spells
+            .cantrips
+            .iter()
+            .any(|known| known == name)
+        {
+            return Err(SpellListError::AlreadyKnown(name.to_string()));
+        }
+        let limit = class_level.cantrip_limit();
+        if class_level.spells.cantrips.len() >= limit as usize {
+            return Err(SpellListError::CantripLimit(limit));
+        }
+
+        self.spell_list_mut(class).cantrips.push(name.to_string());
+        Ok(())
+    }
+
+    /// Learns a spell for classes with spells known, or copies it into a
+    /// wizard's spellbook
+    pub fn learn_spell(&mut self, class: Class, name: &str) -> Result<(), SpellListError> {
+        let spells = Spell::database().map_err(|e| SpellListError::Database(e.to_string()))?;
+        let spell = self.check_spell(class, name, spells)?;
+        if spell.is_cantrip() {
+            return Err(SpellListError::IsACantrip(name.to_string()));
+        }
+        let class_level = self.class_level_for(class)?;
+        match class_level.spell_list_model() {
+            Some(SpellListModel::Known) => {
+                if class_level.spells.known.iter().any(|known| known == name) {
+                    return Err(SpellListError::AlreadyKnown(name.to_string()));
+                }
+                let limit = class_level.known_limit().unwrap_or(0);
+                if class_level.spells.known.len() >= limit as usize {
+                    return Err(SpellListError::KnownLimit(limit));
+                }
+                self.spell_list_mut(class).known.push(name.to_string());
+            }
+            Some(SpellListModel::Spellbook) => {
+                if class_level
+                    .spells
+                    .spellbook
+                    .iter()
+                    .any(|known| known == name)
+                {
+                    return Err(SpellListError::AlreadyKnown(name.to_string()));
+                }
+                self.spell_list_mut(class).spellbook.push(name.to_string());
+            }
+            _ => return Err(SpellListError::WrongModel(class)),
+        }
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    /// Replaces a known spell with another, as classes with spells known
+    /// may do when they gain a level
+    pub fn swap_spell(&mut self, class: Class, old: &str, new: &str) -> Result<(), SpellListError> {
+        let spells = Spell::database().map_err(|e| SpellListError::Database(e.to_string()))?;
+        let class_level = self.class_level_for(class)?;
+        if class_level.spell_list_model() != Some(SpellListModel::Known) {
+            return Err(SpellListError::WrongModel(class));
+        }
+        let Some(index) = class_level
+            .spells
+            .known
+            .iter()
+            .position(|known| known == old)
+        else {
+            return Err(SpellListError::NotKnown(old.to_string()));
+        };
+        if class_level.spells.known.iter().any(|known| known == new) {
+            return Err(SpellListError::AlreadyKnown(new.to_string()));
+        }
+        let spell = self.check_spell(class, new, spells)?;
+        if spell.is_cantrip() {
+            return Err(SpellListError::IsACantrip(new.to_string()));
+        }
+
+        self.spell_list_mut(class).known[index] = new.to_string();
+        Ok(())
+    }
+
+    /// Prepares a spell from the class list, or from the spellbook for wizards
+    pub fn prepare_spell(&mut self, class: Class, name: &str) -> Result<(), SpellListError> {
+        let spells = Spell::database().map_err(|e| SpellListError::Database(e.to_string()))?;
+        let spell = self.check_spell(class, name, spells)?;
+        if spell.is_cantrip() {
+            return Err(SpellListError::IsACantrip(name.to_string()));
+        }
+        let class_level = self.class_level_for(class)?;
+        match class_level.spell_list_model() {
+            Some(SpellListModel::Prepared) => {}
+            Some(SpellListModel::Spellbook) => {
+                if !class_level
+                    .spells
+                    .spellbook
+                    .iter()
+                    .any(|known| known == name)
+                {
+                    return Err(SpellListError::NotInSpellbook(name.to_string()));
+                }
+            }
+            _ => return Err(SpellListError::WrongModel(class)),
+        }
+        if class_level
+            .spells
+            .prepared
+            .iter()
+            .any(|known| known == name)
+        {
+            return Err(SpellListError::AlreadyKnown(name.to_string()));
+        }
+        let limit = self.prepared_limit(class)?;
+        if self.class_level_for(class)?.spells.prepared.len() >= limit as usize {
+            return Err(SpellListError::PreparedLimit(limit));
+        }
+
+        self.spell_list_mut(class).prepared.push(name.to_string());
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn unprepare_spell(&mut self, class: Class, name: &str) -> Result<(), SpellListError> {
+        let class_level = self.class_level_for(class)?;
+        let Some(index) = class_level
+            .spells
+            .prepared
+            .iter()
+            .position(|known| known == name)
+        else {
+            return Err(SpellListError::NotKnown(name.to_string()));
+        };
+
+        self.spell_list_mut(class).prepared.remove(index);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    /// Cantrips plus the spells known or prepared, across all classes
+    pub fn castable_spells(&self) -> Vec<String> {
+        let mut castable: Vec<String> = Vec::new();
+        for class_level in &self.classes {
+            let spells = &class_level.spells;
+            for name in spells
+                .cantrips
+                .iter()
+                .chain(&spells.known)
+                .chain(&spells.prepared)
+            {
+                if !castable.contains(name) {
+                    castable.push(name.clone());
+                }
+            }
+        }
+        castable
+    }
+
+    /// Fills every free cantrip, known, spellbook and prepared spell slot
+    /// with random picks from the class lists
+    pub fn choose_random_spells(&mut self, seed: u64) -> Result<(), SpellListError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let spells = Spell::database().map_err(|e| SpellListError::Database(e.to_string()))?;
+        let classes: Vec<Class> = self
+            .classes
+            .iter()
+            .filter(|class_level| class_level.spell_list_model().is_some())
+            .map(|class_level| class_level.class)
+            .collect();
+
+        for class in classes {
+            let Ok(class_level) = self.class_level_for(class) else {
+                continue;
+            };
+            let list_class = class_level.spell_list_class();
+            let max_level = class_level.max_spell_level();
+            let model = class_level.spell_list_model();
+            let missing_from_spellbook = (class_level.free_spellbook_spells() as usize)
+                .saturating_sub(class_level.spells.spellbook.len());
+
+            let mut cantrips: Vec<String> = SpellQuery::default()
+                .class(list_class)
+                .level(0)
+                .run(spells)
+                .into_iter()
+                .map(|spell| spell.name.clone())
+                .collect();
+            cantrips.shuffle(&mut rng);
+            for name in cantrips {
+                let _ = self.learn_cantrip(class, &name);
+            }
+
+            if max_level == 0 {
+                continue;
+            }
+            let mut leveled: Vec<String> = (1..=max_level)
+                .flat_map(|level| {
+                    SpellQuery::default()
+                        .class(list_class)
+                        .level(level)
+                        .run(spells)
+                })
+                .map(|spell| spell.name.clone())
+                .collect();
+            leveled.shuffle(&mut rng);
+
+            match model {
+                Some(SpellListModel::Known) => {
+                    for name in &leveled {
+                        let _ = self.learn_spell(class, name);
+                    }
+                }
+                Some(SpellListModel::Spellbook) => {
+                    for name in leveled.iter().take(missing_from_spellbook) {
+                        let _ = self.learn_spell(class, name);
+                    }
+                    let mut spellbook = self
+                        .class_level_for(class)
+                        .map(|class_level| class_level.spells.spellbook.clone())
+                        .unwrap_or_default();
+                    spellbook.shuffle(&mut rng);
+                    for name in &spellbook {
+                        let _ = self.prepare_spell(class, name);
+                    }
+                }
+                Some(SpellListModel::Prepared) => {
+                    for name in &leveled {
+                        let _ = self.prepare_spell(class, name);
+                    }
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::character_of;
+
+    /// A level 1 caster of `class` with every spell list emptied
+    fn caster(class: Class) -> Character {
+        let mut character = character_of(class);
+        character.classes[0].spells = SpellList::default();
+        character
+    }
+
+    #[test]
+    fn wizard_prepares_from_the_spellbook() {
+        let mut wizard = caster(Class::Wizard);
+        assert_eq!(
+            wizard.prepare_spell(Class::Wizard, "Magic Missile"),
+            Err(SpellListError::NotInSpellbook("Magic Missile".to_string()))
+        );
+        assert_eq!(
+            wizard.learn_spell(Class::Wizard, "Cure Wounds"),
+            Err(SpellListError::NotOnClassList {
+                spell: "Cure Wounds".to_string(),
+                class: Class::Wizard
+            })
+        );
+        assert_eq!(
+            wizard.learn_spell(Class::Wizard, "Fireball"),
+            Err(SpellListError::LevelTooHigh {
+                spell: "Fireball".to_string(),
+                max_level: 1
+            })
+        );
+
+        wizard.learn_spell(Class::Wizard, "Magic Missile").unwrap();
+        wizard.learn_spell(Class::Wizard, "Sleep").unwrap();
+        // INT 10 adds nothing to the one level
+        assert_eq!(wizard.prepared_limit(Class::Wizard), Ok(1));
+        assert_eq!(wizard.prepare_spell(Class::Wizard, "Magic Missile"), Ok(()));
+        assert_eq!(
+            wizard.prepare_spell(Class::Wizard, "Sleep"),
+            Err(SpellListError::PreparedLimit(1))
+        );
+    }
+
+    #[test]
+    fn sorcerer_knows_a_fixed_number_of_spells() {
+        let mut sorcerer = caster(Class::Sorcerer);
+        sorcerer
+            .learn_spell(Class::Sorcerer, "Magic Missile")
+            .unwrap();
+        sorcerer.learn_spell(Class::Sorcerer, "Sleep").unwrap();
+        assert_eq!(
+            sorcerer.learn_spell(Class::Sorcerer, "Shield"),
+            Err(SpellListError::KnownLimit(2))
+        );
+        assert_eq!(
+            sorcerer.prepare_spell(Class::Sorcerer, "Sleep"),
+            Err(SpellListError::WrongModel(Class::Sorcerer))
+        );
+
+        assert_eq!(
+            sorcerer.swap_spell(Class::Sorcerer, "Sleep", "Shield"),
+            Ok(())
+        );
+        assert_eq!(
+            sorcerer.classes[0].spells.known,
+            vec!["Magic Missile".to_string(), "Shield".to_string()]
+        );
+    }
+
+    #[test]
+    fn cleric_prepares_from_the_class_list() {
+        let mut cleric = caster(Class::Cleric);
+        assert_eq!(
+            cleric.learn_spell(Class::Cleric, "Cure Wounds"),
+            Err(SpellListError::WrongModel(Class::Cleric))
+        );
+        assert_eq!(cleric.prepare_spell(Class::Cleric, "Cure Wounds"), Ok(()));
+
+        for cantrip in ["Sacred Flame", "Light", "Guidance"] {
+            cleric.learn_cantrip(Class::Cleric, cantrip).unwrap();
+        }
+        assert_eq!(
+            cleric.learn_cantrip(Class::Cleric, "Thaumaturgy"),
+            Err(SpellListError::CantripLimit(3))
+        );
+        assert_eq!(
+            cleric.learn_cantrip(Class::Cleric, "Bless"),
+            Err(SpellListError::NotACantrip("Bless".to_string()))
+        );
+
+        let mut fighter = character_of(Class::Fighter);
+        assert_eq!(
+            fighter.learn_cantrip(Class::Fighter, "Light"),
+            Err(SpellListError::NotACaster(Class::Fighter))
+        );
+    }
+
+    #[test]
+    fn spell_lists_are_saved_with_the_character() {
+        let mut wizard = caster(Class::Wizard);
+        wizard.learn_spell(Class::Wizard, "Shield").unwrap();
+        wizard.prepare_spell(Class::Wizard, "Shield").unwrap();
+
+        let json = serde_json::to_string(&wizard).unwrap();
+        let loaded: Character = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.classes[0].spells.spellbook,
+            vec!["Shield".to_string()]
+        );
+        assert_eq!(
+            loaded.classes[0].spells.prepared,
+            vec!["Shield".to_string()]
+        );
+    }
+}
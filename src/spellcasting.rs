@@ -0,0 +1,398 @@
+use crate::{
+    ability::Abilities, character::Character, class::Class, multiclass::ClassLevel,
+    subclass::Subclass,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+/// Spell slots per spell level (1st to 9th) for a full caster of each level
+const FULL_CASTER_SLOTS: [[u8; 9]; 21] = [
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [2, 0, 0, 0, 0, 0, 0, 0, 0],
+    [3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [4, 2, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 2, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 1, 0, 0, 0, 0, 0],
+    [4, 3, 3, 2, 0, 0, 0, 0, 0],
+    [4, 3, 3, 3, 1, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 2, 1, 1],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasterType {
+    Full,
+    Half,
+    Third,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpellSlots {
+    pub current: [u8; 9],
+    pub max: [u8; 9],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpellcastingError {
+    NotACaster,
+    InvalidSlotLevel(u8),
+    NoSlot(u8),
+    UnknownSpell(String),
+    NotKnown(String),
+    SlotBelowSpellLevel {
+        spell_level: u8,
+        slot_level: u8,
+    },
+    /// Worn armor the caster isn't proficient with
+    ArmorNotProficient(String),
+    /// data/spells.toml couldn't be loaded
+    Database(String),
+}
+
+impl fmt::Display for SpellcastingError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SpellcastingError::NotACaster => write!(f, "Character can't cast spells"),
+            SpellcastingError::InvalidSlotLevel(level) => {
+                write!(f, "There are no level {} spell slots", level)
+            }
+            SpellcastingError::NoSlot(level) => {
+                write!(f, "No spell slots of level {} or higher left", level)
+            }
+            SpellcastingError::UnknownSpell(spell) => write!(f, "Unknown spell: {}", spell),
+            SpellcastingError::NotKnown(spell) => {
+                write!(f, "{} is not known or prepared", spell)
+            }
+            SpellcastingError::SlotBelowSpellLevel {
+                spell_level,
+                slot_level,
+            } => write!(
+                f,
+                "A level {} spell can't be cast with a level {} slot",
+                spell_level, slot_level
+            ),
+            SpellcastingError::ArmorNotProficient(armor) => {
+                write!(
+                    f,
+                    "Can't cast spells while wearing {} without proficiency",
+                    armor
+                )
+            }
+            SpellcastingError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl CasterType {
+    /// Caster level a single-classed character of `level` uses on the full caster table
+    pub fn caster_level(&self, level: u8) -> u8 {
+        match self {
+            CasterType::Full => level,
+            CasterType::Half if level >= 2 => level.div_ceil(2),
+            CasterType::Third if level >= 3 => level.div_ceil(3),
+            _ => 0,
+        }
+    }
+
+    /// Multiclass spellcasters round every class's contribution down
+    fn multiclass_caster_level(&self, level: u8) -> u8 {
+        match self {
+            CasterType::Full => level,
+            CasterType::Half => level / 2,
+            CasterType::Third => level / 3,
+        }
+    }
+}
+
+impl SpellSlots {
+    fn with_max(max: [u8; 9]) -> Self {
+        SpellSlots { current: max, max }
+    }
+
+    fn index(level: u8) -> Result<usize, SpellcastingError> {
+        match level {
+            1..=9 => Ok(level as usize - 1),
+            _ => Err(SpellcastingError::InvalidSlotLevel(level)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.iter().all(|slots| *slots == 0)
+    }
+
+    /// Uses one slot of `level`, returning how many are left
+    pub fn expend(&mut self, level: u8) -> Result<u8, SpellcastingError> {
+        let index = SpellSlots::index(level)?;
+        if self.current[index] == 0 {
+            return Err(SpellcastingError::NoSlot(level));
+        }
+        self.current[index] -= 1;
+        Ok(self.current[index])
+    }
+
+    /// Regains up to `amount` slots of `level`, returning how many came back
+    pub fn recover(&mut self, level: u8, amount: u8) -> Result<u8, SpellcastingError> {
+        let index = SpellSlots::index(level)?;
+        let regained = amount.min(self.max[index] - self.current[index]);
+        self.current[index] += regained;
+        Ok(regained)
+    }
+
+    /// Regains every slot, returning how many came back
+    pub fn recover_all(&mut self) -> u8 {
+        let mut regained = 0;
+        for (current, max) in self.current.iter_mut().zip(self.max) {
+            regained += max - *current;
+            *current = max;
+        }
+        regained
+    }
+}
+
+impl Class {
+    pub fn spellcasting_ability(&self) -> Option<Abilities> {
+        match self {
+            Class::Bard | Class::Paladin | Class::Sorcerer | Class::Warlock => {
+                Some(Abilities::Charisma)
+            }
+            Class::Cleric | Class::Druid | Class::Ranger => Some(Abilities::Wisdom),
+            Class::Wizard => Some(Abilities::Intelligence),
+            Class::Barbarian | Class::Fighter | Class::Monk | Class::Rogue => None,
+        }
+    }
+
+    /// Warlocks are left out, Pact Magic doesn't use these slots
+    pub fn caster_type(&self) -> Option<CasterType> {
+        match self {
+            Class::Bard | Class::Cleric | Class::Druid | Class::Sorcerer | Class::Wizard => {
+                Some(CasterType::Full)
+            }
+            Class::Paladin | Class::Ranger => Some(CasterType::Half),
+            _ => None,
+        }
+    }
+}
+
+impl Subclass {
+    /// Subclasses that turn a non-caster class into a third caster
+    pub fn spellcasting_ability(&self) -> Option<Abilities> {
+        match self {
+            Subclass::EldritchKnight | Subclass::ArcaneTrickster => Some(Abilities::Intelligence),
+            _ => None,
+        }
+    }
+}
+
+impl ClassLevel {
+    /// Caster type of the class, or of the subclass for Eldritch Knights
+    /// and Arcane Tricksters
+    pub fn caster_type(&self) -> Option<CasterType> {
+        self.class.caster_type().or_else(|| {
+            self.subclass
+                .and_then(|subclass| subclass.spellcasting_ability())
+                .map(|_| CasterType::Third)
+        })
+    }
+
+    /// Highest spell level the class alone gives slots for
+    pub fn max_spell_level(&self) -> u8 {
+        if let Some((_, slot_level)) = self.class.pact_magic_slots(self.level) {
+            return slot_level;
+        }
+        let Some(caster_type) = self.caster_type() else {
+            return 0;
+        };
+        let slots = FULL_CASTER_SLOTS[caster_type.caster_level(self.level) as usize];
+        slots.iter().take_while(|slots| **slots > 0).count() as u8
+    }
+}
+
+impl fmt::Display for SpellSlots {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let levels = [
+            "1st", "2nd", "3rd", "4th", "5th", "6th", "7th", "8th", "9th",
+        ];
+        let slots: Vec<String> = levels
+            .iter()
+            .zip(self.current.iter().zip(self.max))
+            .filter(|(_, (_, max))| *max > 0)
+            .map(|(level, (current, max))| format!("{} {}/{}", level, current, max))
+            .collect();
+        write!(f, "{}", slots.join("  "))
+    }
+}
+
+impl Character {
+    /// Every class that casts spells along with its spellcasting ability
+    pub fn spellcasting_classes(&self) -> Vec<(Class, Abilities)> {
+        self.classes
+            .iter()
+            .filter_map(|class_level| {
+                let ability = class_level.class.spellcasting_ability().or_else(|| {
+                    class_level
+                        .subclass
+                        .and_then(|subclass| subclass.spellcasting_ability())
+                })?;
+                Some((class_level.class, ability))
+            })
+            .collect()
+    }
+
+    fn caster_levels(&self) -> Vec<(CasterType, u8)> {
+        self.classes
+            .iter()
+            .filter_map(|class_level| Some((class_level.caster_type()?, class_level.level)))
+            .collect()
+    }
+
+    /// Level used on the full caster slot table, combining classes with
+    /// the multiclass spellcaster rules
+    pub fn caster_level(&self) -> u8 {
+        match self.caster_levels().as_slice() {
+            [] => 0,
+            [(caster_type, level)] => caster_type.caster_level(*level),
+            casters => casters
+                .iter()
+                .map(|(caster_type, level)| caster_type.multiclass_caster_level(*level))
+                .sum::<u8>()
+                .min(20),
+        }
+    }
+
+    pub fn spell_slot_maximums(&self) -> [u8; 9] {
+        FULL_CASTER_SLOTS[self.caster_level() as usize]
+    }
+
+    /// Rebuilds the slots after a level change, keeping expended slots expended
+    pub fn refresh_spell_slots(&mut self) {
+        let mut slots = SpellSlots::with_max(self.spell_slot_maximums());
+        for index in 0..9 {
+            let spent = self.spell_slots.max[index].saturating_sub(self.spell_slots.current[index]);
+            slots.current[index] = slots.max[index].saturating_sub(spent);
+        }
+        self.spell_slots = slots;
+    }
+
+    /// 8 + proficiency bonus + spellcasting ability modifier
+    pub fn spell_save_dc(&self, ability: Abilities) -> i8 {
+        8 + self.spell_attack_bonus(ability)
+    }
+
+    pub fn spell_attack_bonus(&self, ability: Abilities) -> i8 {
+        self.proficiency_bonus() + self.stats.get_ability_modifier(ability)
+    }
+
+    #[allow(dead_code)]
+    /// Uses a spell slot of `level`, returning how many are left
+    pub fn expend_spell_slot(&mut self, level: u8) -> Result<u8, SpellcastingError> {
+        if self.spell_slots.is_empty() {
+            return Err(SpellcastingError::NotACaster);
+        }
+        self.spell_slots.expend(level)
+    }
+
+    #[allow(dead_code)]
+    pub fn recover_spell_slot(&mut self, level: u8, amount: u8) -> Result<u8, SpellcastingError> {
+        if self.spell_slots.is_empty() {
+            return Err(SpellcastingError::NotACaster);
+        }
+        self.spell_slots.recover(level, amount)
+    }
+
+    /// Regains all spell slots as on a long rest
+    pub fn recover_spell_slots(&mut self) -> u8 {
+        self.spell_slots.recover_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character_of, set_levels};
+
+    fn caster(levels: &[(Class, u8)]) -> Character {
+        let mut character = character_of(levels[0].0);
+        set_levels(&mut character, levels);
+        character
+    }
+
+    fn character_with_subclass(class: Class, level: u8, subclass: Subclass) -> Character {
+        let mut character = caster(&[(class, level)]);
+        character.classes[0].subclass = Some(subclass);
+        character.refresh_spell_slots();
+        character
+    }
+
+    #[test]
+    fn full_caster_table_matches_phb() {
+        assert_eq!(FULL_CASTER_SLOTS[1], [2, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(FULL_CASTER_SLOTS[5], [4, 3, 2, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(FULL_CASTER_SLOTS[20], [4, 3, 3, 3, 3, 2, 2, 1, 1]);
+        for level in 1..20 {
+            let slots = FULL_CASTER_SLOTS[level];
+            let next = FULL_CASTER_SLOTS[level + 1];
+            assert!(slots.iter().zip(next).all(|(now, later)| *now <= later));
+        }
+    }
+
+    #[test]
+    fn single_class_caster_levels_round_up() {
+        assert_eq!(CasterType::Full.caster_level(7), 7);
+        assert_eq!(CasterType::Half.caster_level(1), 0);
+        assert_eq!(CasterType::Half.caster_level(2), 1);
+        assert_eq!(CasterType::Half.caster_level(5), 3);
+        assert_eq!(CasterType::Third.caster_level(2), 0);
+        assert_eq!(CasterType::Third.caster_level(3), 1);
+        assert_eq!(CasterType::Third.caster_level(20), 7);
+    }
+
+    #[test]
+    fn multiclass_caster_levels_round_down() {
+        let character = caster(&[(Class::Wizard, 3), (Class::Paladin, 5)]);
+        assert_eq!(character.caster_level(), 5);
+        assert_eq!(character.spell_slots.max, FULL_CASTER_SLOTS[5]);
+
+        let character = character_with_subclass(Class::Fighter, 7, Subclass::EldritchKnight);
+        assert_eq!(character.caster_level(), 3);
+    }
+
+    #[test]
+    fn warlocks_and_non_casters_have_no_slots() {
+        assert_eq!(caster(&[(Class::Warlock, 5)]).caster_level(), 0);
+        assert_eq!(caster(&[(Class::Fighter, 5)]).caster_level(), 0);
+        assert!(caster(&[(Class::Barbarian, 3)]).spell_slots.is_empty());
+    }
+
+    #[test]
+    fn slots_are_expended_and_recovered() {
+        let mut slots = SpellSlots::with_max(FULL_CASTER_SLOTS[3]);
+        assert_eq!(slots.expend(2), Ok(1));
+        assert_eq!(slots.expend(2), Ok(0));
+        assert_eq!(slots.expend(2), Err(SpellcastingError::NoSlot(2)));
+        assert_eq!(slots.expend(0), Err(SpellcastingError::InvalidSlotLevel(0)));
+        assert_eq!(slots.recover(2, 5), Ok(2));
+        slots.expend(1).unwrap();
+        assert_eq!(slots.recover_all(), 1);
+    }
+
+    #[test]
+    fn level_changes_keep_expended_slots() {
+        let mut character = caster(&[(Class::Wizard, 2)]);
+        character.expend_spell_slot(1).unwrap();
+        character.classes[0].level = 3;
+        character.refresh_spell_slots();
+        assert_eq!(character.spell_slots.current[0], 3);
+        assert_eq!(character.spell_slots.current[1], 2);
+    }
+}
@@ -0,0 +1,606 @@
+use crate::{
+    character::Character,
+    class::Class,
+    dice::Dice,
+    equipment::{Armor, Gear, Weapon, WeaponRange, WeaponType},
+    inventory::{Inventory, Item},
+    money::{Money, MoneyType},
+};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use std::collections::HashMap;
+use std::fmt::{self, Formatter};
+use std::io;
+
+/// One item of a starting equipment option
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartingItem {
+    Armor(&'static str),
+    Weapon(&'static str),
+    /// "any simple weapon", "any martial melee weapon" and so on
+    AnyWeapon(WeaponType, Option<WeaponRange>),
+    Gear(&'static str),
+}
+
+/// A PHB "(a) ... or (b) ..." line, the character takes exactly one option.
+/// Fixed items are a choice with a single option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquipmentChoice {
+    pub options: Vec<Vec<StartingItem>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartingEquipmentError {
+    WrongChoiceCount {
+        expected: usize,
+        got: usize,
+    },
+    NoSuchOption {
+        choice: usize,
+        option: usize,
+    },
+    MissingWeaponPick,
+    UnknownItem(String),
+    WeaponNotAllowed(String),
+    NotProficient(String),
+    /// An item database in data/ couldn't be loaded
+    Database(String),
+}
+
+impl fmt::Display for StartingEquipmentError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StartingEquipmentError::WrongChoiceCount { expected, got } => {
+                write!(f, "Expected {} equipment choices, got {}", expected, got)
+            }
+            StartingEquipmentError::NoSuchOption { choice, option } => {
+                write!(f, "Choice {} has no option {}", choice, option)
+            }
+            StartingEquipmentError::MissingWeaponPick => {
+                write!(f, "A weapon of your choice needs to be named")
+            }
+            StartingEquipmentError::UnknownItem(name) => write!(f, "Unknown item: {}", name),
+            StartingEquipmentError::WeaponNotAllowed(name) => {
+                write!(f, "{} doesn't fit the weapon choice", name)
+            }
+            StartingEquipmentError::NotProficient(name) => {
+                write!(f, "Character is not proficient with {}", name)
+            }
+            StartingEquipmentError::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+fn choice(options: Vec<Vec<StartingItem>>) -> EquipmentChoice {
+    EquipmentChoice { options }
+}
+
+fn fixed(items: Vec<StartingItem>) -> EquipmentChoice {
+    EquipmentChoice {
+        options: vec![items],
+    }
+}
+
+impl Class {
+    /// PHB starting equipment as a list of choices
+    pub fn starting_equipment(&self) -> Vec<EquipmentChoice> {
+        use StartingItem::*;
+        let simple = AnyWeapon(WeaponType::Simple, None);
+        let simple_melee = AnyWeapon(WeaponType::Simple, Some(WeaponRange::Melee));
+        let martial = AnyWeapon(WeaponType::Martial, None);
+        let martial_melee = AnyWeapon(WeaponType::Martial, Some(WeaponRange::Melee));
+
+        match self {
+            Class::Barbarian => vec![
+                choice(vec![vec![Weapon("Greataxe")], vec![martial_melee]]),
+                choice(vec![
+                    vec![Weapon("Handaxe"), Weapon("Handaxe")],
+                    vec![simple],
+                ]),
+                fixed(vec![
+                    Gear("Explorer's Pack"),
+                    Weapon("Javelin"),
+                    Weapon("Javelin"),
+                    Weapon("Javelin"),
+                    Weapon("Javelin"),
+                ]),
+            ],
+            Class::Bard => vec![
+                choice(vec![
+                    vec![Weapon("Rapier")],
+                    vec![Weapon("Longsword")],
+                    vec![simple],
+                ]),
+                choice(vec![
+                    vec![Gear("Diplomat's Pack")],
+                    vec![Gear("Entertainer's Pack")],
+                ]),
+                choice(vec![
+                    vec![Gear("Lute")],
+                    vec![Gear("Drum")],
+                    vec![Gear("Flute")],
+                    vec![Gear("Horn")],
+                    vec![Gear("Lyre")],
+                    vec![Gear("Viol")],
+                ]),
+                fixed(vec![Armor("Leather"), Weapon("Dagger")]),
+            ],
+            Class::Cleric => vec![
+                choice(vec![vec![Weapon("Mace")], vec![Weapon("Warhammer")]]),
+                choice(vec![
+                    vec![Armor("Scale Mail")],
+                    vec![Armor("Leather")],
+                    vec![Armor("Chain Mail")],
+                ]),
+                choice(vec![
+                    vec![Weapon("Light Crossbow"), Gear("Crossbow Bolts")],
+                    vec![simple],
+                ]),
+                choice(vec![
+                    vec![Gear("Priest's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Armor("Shield"), Gear("Holy Symbol")]),
+            ],
+            Class::Druid => vec![
+                choice(vec![vec![Armor("Shield")], vec![simple]]),
+                choice(vec![vec![Weapon("Scimitar")], vec![simple_melee]]),
+                fixed(vec![
+                    Armor("Leather"),
+                    Gear("Explorer's Pack"),
+                    Gear("Druidic Focus"),
+                ]),
+            ],
+            Class::Fighter => vec![
+                choice(vec![
+                    vec![Armor("Chain Mail")],
+                    vec![Armor("Leather"), Weapon("Longbow"), Gear("Arrows")],
+                ]),
+                choice(vec![
+                    vec![martial.clone(), Armor("Shield")],
+                    vec![martial.clone(), martial],
+                ]),
+                choice(vec![
+                    vec![Weapon("Light Crossbow"), Gear("Crossbow Bolts")],
+                    vec![Weapon("Handaxe"), Weapon("Handaxe")],
+                ]),
+                choice(vec![
+                    vec![Gear("Dungeoneer's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+            ],
+            Class::Monk => vec![
+                choice(vec![vec![Weapon("Shortsword")], vec![simple]]),
+                choice(vec![
+                    vec![Gear("Dungeoneer's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Weapon("Dart"); 10]),
+            ],
+            Class::Paladin => vec![
+                choice(vec![
+                    vec![martial.clone(), Armor("Shield")],
+                    vec![martial.clone(), martial],
+                ]),
+                choice(vec![vec![Weapon("Javelin"); 5], vec![simple_melee]]),
+                choice(vec![
+                    vec![Gear("Priest's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Armor("Chain Mail"), Gear("Holy Symbol")]),
+            ],
+            Class::Ranger => vec![
+                choice(vec![vec![Armor("Scale Mail")], vec![Armor("Leather")]]),
+                choice(vec![
+                    vec![Weapon("Shortsword"), Weapon("Shortsword")],
+                    vec![simple_melee.clone(), simple_melee],
+                ]),
+                choice(vec![
+                    vec![Gear("Dungeoneer's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Weapon("Longbow"), Gear("Arrows")]),
+            ],
+            Class::Rogue => vec![
+                choice(vec![vec![Weapon("Rapier")], vec![Weapon("Shortsword")]]),
+                choice(vec![
+                    vec![Weapon("Shortbow"), Gear("Arrows")],
+                    vec![Weapon("Shortsword")],
+                ]),
+                choice(vec![
+                    vec![Gear("Burglar's Pack")],
+                    vec![Gear("Dungeoneer's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![
+                    Armor("Leather"),
+                    Weapon("Dagger"),
+                    Weapon("Dagger"),
+                    Gear("Thieves' Tools"),
+                ]),
+            ],
+            Class::Sorcerer => vec![
+                choice(vec![
+                    vec![Weapon("Light Crossbow"), Gear("Crossbow Bolts")],
+                    vec![simple],
+                ]),
+                choice(vec![
+                    vec![Gear("Component Pouch")],
+                    vec![Gear("Arcane Focus")],
+                ]),
+                choice(vec![
+                    vec![Gear("Dungeoneer's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Weapon("Dagger"), Weapon("Dagger")]),
+            ],
+            Class::Warlock => vec![
+                choice(vec![
+                    vec![Weapon("Light Crossbow"), Gear("Crossbow Bolts")],
+                    vec![simple.clone()],
+                ]),
+                choice(vec![
+                    vec![Gear("Component Pouch")],
+                    vec![Gear("Arcane Focus")],
+                ]),
+                choice(vec![
+                    vec![Gear("Scholar's Pack")],
+                    vec![Gear("Dungeoneer's Pack")],
+                ]),
+                fixed(vec![
+                    Armor("Leather"),
+                    simple,
+                    Weapon("Dagger"),
+                    Weapon("Dagger"),
+                ]),
+            ],
+            Class::Wizard => vec![
+                choice(vec![vec![Weapon("Quarterstaff")], vec![Weapon("Dagger")]]),
+                choice(vec![
+                    vec![Gear("Component Pouch")],
+                    vec![Gear("Arcane Focus")],
+                ]),
+                choice(vec![
+                    vec![Gear("Scholar's Pack")],
+                    vec![Gear("Explorer's Pack")],
+                ]),
+                fixed(vec![Gear("Spellbook")]),
+            ],
+        }
+    }
+
+    /// Dice and multiplier for taking starting gold instead of equipment
+    pub fn starting_gold(&self) -> (Dice, u32) {
+        match self {
+            Class::Barbarian | Class::Druid => (Dice::new(2, 4, 0), 10),
+            Class::Bard | Class::Cleric | Class::Fighter | Class::Paladin | Class::Ranger => {
+                (Dice::new(5, 4, 0), 10)
+            }
+            Class::Monk => (Dice::new(5, 4, 0), 1),
+            Class::Rogue | Class::Warlock | Class::Wizard => (Dice::new(4, 4, 0), 10),
+            Class::Sorcerer => (Dice::new(3, 4, 0), 10),
+        }
+    }
+}
+
+// Item catalogues loaded once per equipment resolution
+struct Catalogue {
+    armor: HashMap<String, Armor>,
+    weapons: HashMap<String, Weapon>,
+    gear: HashMap<String, Gear>,
+}
+
+impl Catalogue {
+    fn load() -> Result<Self, StartingEquipmentError> {
+        let database = |e: io::Error| StartingEquipmentError::Database(e.to_string());
+        Ok(Catalogue {
+            armor: Armor::load_armor_database().map_err(database)?,
+            weapons: Weapon::load_weapon_database().map_err(database)?,
+            gear: Gear::load_gear_database().map_err(database)?,
+        })
+    }
+
+    /// Weapons the character could take for an "any weapon" item, sorted by name
+    fn weapons_matching(
+        &self,
+        character: &Character,
+        weapon_type: WeaponType,
+        range: Option<WeaponRange>,
+    ) -> Vec<&Weapon> {
+        let mut weapons: Vec<&Weapon> = self
+            .weapons
+            .values()
+            .filter(|w| fits(w, weapon_type, range) && character.is_proficient_with(w))
+            .collect();
+        weapons.sort_by(|a, b| a.name.cmp(&b.name));
+        weapons
+    }
+}
+
+fn fits(weapon: &Weapon, weapon_type: WeaponType, range: Option<WeaponRange>) -> bool {
+    weapon.weapon_type == weapon_type && range.is_none_or(|range| weapon.weapon_range == range)
+}
+
+#[derive(Default)]
+struct Kit {
+    armor: Vec<Armor>,
+    weapons: Vec<Weapon>,
+    gear: Vec<Gear>,
+}
+
+impl Character {
+    // Items of an option the character can't use make the option unavailable
+    fn add_starting_item(
+        &self,
+        kit: &mut Kit,
+        catalogue: &Catalogue,
+        item: &StartingItem,
+        weapon_pick: &mut dyn FnMut(
+            WeaponType,
+            Option<WeaponRange>,
+        ) -> Result<Weapon, StartingEquipmentError>,
+    ) -> Result<(), StartingEquipmentError> {
+        match item {
+            StartingItem::Armor(name) => {
+                let armor = catalogue
+                    .armor
+                    .get(*name)
+                    .ok_or_else(|| StartingEquipmentError::UnknownItem(name.to_string()))?;
+                if !self.armor_proficiencies().contains(&armor.armor_type) {
+                    return Err(StartingEquipmentError::NotProficient(armor.name.clone()));
+                }
+                kit.armor.push(armor.clone());
+            }
+            StartingItem::Weapon(name) => {
+                let weapon = catalogue
+                    .weapons
+                    .get(*name)
+                    .ok_or_else(|| StartingEquipmentError::UnknownItem(name.to_string()))?;
+                if !self.is_proficient_with(weapon) {
+                    return Err(StartingEquipmentError::NotProficient(weapon.name.clone()));
+                }
+                kit.weapons.push(weapon.clone());
+            }
+            StartingItem::AnyWeapon(weapon_type, range) => {
+                kit.weapons.push(weapon_pick(*weapon_type, *range)?);
+            }
+            StartingItem::Gear(name) => {
+                let gear = catalogue
+                    .gear
+                    .get(*name)
+                    .ok_or_else(|| StartingEquipmentError::UnknownItem(name.to_string()))?;
+                kit.gear.push(gear.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn equip_kit(&mut self, kit: Kit) {
+        self.inventory = Inventory::default();
+        self.inventory
+            .carried
+            .extend(kit.armor.into_iter().map(Item::Armor));
+        self.inventory
+            .carried
+            .extend(kit.weapons.into_iter().map(Item::Weapon));
+        self.inventory
+            .carried
+            .extend(kit.gear.into_iter().map(Item::Gear));
+        self.equip_carried_gear();
+    }
+
+    #[allow(dead_code)]
+    /// Takes the starting equipment of the starting class. `picks` holds the
+    /// option index for every choice and `weapons` names the weapons for the
+    /// "any weapon" items, in order.
+    pub fn choose_starting_equipment(
+        &mut self,
+        picks: &[usize],
+        weapons: &[&str],
+    ) -> Result<(), StartingEquipmentError> {
+        let choices = self.primary_class().starting_equipment();
+        if picks.len() != choices.len() {
+            return Err(StartingEquipmentError::WrongChoiceCount {
+                expected: choices.len(),
+                got: picks.len(),
+            });
+        }
+
+        let catalogue = Catalogue::load()?;
+        let mut named = weapons.iter();
+        let mut weapon_pick = |weapon_type, range| {
+            let name = named
+                .next()
+                .ok_or(StartingEquipmentError::MissingWeaponPick)?;
+            let weapon = catalogue
+                .weapons
+                .get(*name)
+                .ok_or_else(|| StartingEquipmentError::UnknownItem(name.to_string()))?;
+            if !fits(weapon, weapon_type, range) {
+                return Err(StartingEquipmentError::WeaponNotAllowed(
+                    weapon.name.clone(),
+                ));
+            }
+            if !self.is_proficient_with(weapon) {
+                return Err(StartingEquipmentError::NotProficient(weapon.name.clone()));
+            }
+            Ok(weapon.clone())
+        };
+
+        let mut kit = Kit::default();
+        for (index, (choice, pick)) in choices.iter().zip(picks).enumerate() {
+            let option = choice
+                .options
+                .get(*pick)
+                .ok_or(StartingEquipmentError::NoSuchOption {
+                    choice: index,
+                    option: *pick,
+                })?;
+            for item in option {
+                self.add_starting_item(&mut kit, &catalogue, item, &mut weapon_pick)?;
+            }
+        }
+
+        self.equip_kit(kit);
+        Ok(())
+    }
+
+    /// Randomly picks among the options the character is proficient with,
+    /// reproducible for the same seed
+    pub fn choose_random_starting_equipment(
+        &mut self,
+        seed: u64,
+    ) -> Result<(), StartingEquipmentError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let catalogue = Catalogue::load()?;
+        let mut kit = Kit::default();
+
+        for choice in self.primary_class().starting_equipment() {
+            let mut options = choice.options.clone();
+            options.shuffle(&mut rng);
+
+            for option in options {
+                let mut option_kit = Kit::default();
+                let mut weapon_pick = |weapon_type, range| {
+                    catalogue
+                        .weapons_matching(self, weapon_type, range)
+                        .choose(&mut rng)
+                        .map(|weapon| (*weapon).clone())
+                        .ok_or(StartingEquipmentError::MissingWeaponPick)
+                };
+                let taken = option.iter().try_for_each(|item| {
+                    self.add_starting_item(&mut option_kit, &catalogue, item, &mut weapon_pick)
+                });
+                if taken.is_ok() {
+                    kit.armor.extend(option_kit.armor);
+                    kit.weapons.extend(option_kit.weapons);
+                    kit.gear.extend(option_kit.gear);
+                    break;
+                }
+            }
+        }
+
+        self.equip_kit(kit);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    /// Takes rolled starting gold instead of the class equipment
+    pub fn take_starting_gold(&mut self) -> u32 {
+        let (dice, multiplier) = self.primary_class().starting_gold();
+        let gold = dice.roll() as u32 * multiplier;
+
+        self.equip_kit(Kit::default());
+        self.current_money = vec![
+            Money {
+                coin_type: MoneyType::Copper,
+                amount: 0,
+            },
+            Money {
+                coin_type: MoneyType::Silver,
+                amount: 0,
+            },
+            Money {
+                coin_type: MoneyType::Gold,
+                amount: gold,
+            },
+        ];
+        gold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::character_of;
+
+    #[test]
+    fn fighter_kit_comes_from_the_item_databases() {
+        let mut fighter = character_of(Class::Fighter);
+        fighter
+            .choose_starting_equipment(&[0, 0, 0, 0], &["Longsword"])
+            .unwrap();
+
+        let inventory = &fighter.inventory;
+        assert_eq!(
+            inventory
+                .body_armor
+                .as_ref()
+                .map(|armor| armor.name.as_str()),
+            Some("Chain Mail")
+        );
+        assert_eq!(
+            inventory.shield.as_ref().map(|armor| armor.name.as_str()),
+            Some("Shield")
+        );
+        assert_eq!(
+            inventory
+                .main_hand
+                .as_ref()
+                .map(|weapon| weapon.name.as_str()),
+            Some("Longsword")
+        );
+        assert!(
+            inventory
+                .carried
+                .iter()
+                .any(|item| item.name() == "Dungeoneer's Pack")
+        );
+    }
+
+    #[test]
+    fn unknown_weapon_pick_is_rejected() {
+        let mut fighter = character_of(Class::Fighter);
+        assert_eq!(
+            fighter.choose_starting_equipment(&[0, 0, 0, 0], &["Lightsaber"]),
+            Err(StartingEquipmentError::UnknownItem(
+                "Lightsaber".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn random_kit_for_every_class_is_reproducible() {
+        for class in [
+            Class::Barbarian,
+            Class::Bard,
+            Class::Cleric,
+            Class::Druid,
+            Class::Fighter,
+            Class::Monk,
+            Class::Paladin,
+            Class::Ranger,
+            Class::Rogue,
+            Class::Sorcerer,
+            Class::Warlock,
+            Class::Wizard,
+        ] {
+            let mut first = character_of(class);
+            first.choose_random_starting_equipment(7).unwrap();
+            let mut second = character_of(class);
+            second.choose_random_starting_equipment(7).unwrap();
+
+            let names = |character: &Character| -> Vec<String> {
+                character
+                    .inventory
+                    .carried
+                    .iter()
+                    .map(|item| item.name().to_string())
+                    .collect()
+            };
+            assert!(!names(&first).is_empty(), "{} has no kit", class);
+            assert_eq!(names(&first), names(&second));
+            // Only armor the class can wear is put on
+            if let Some(armor) = &first.inventory.body_armor {
+                assert!(first.armor_proficiencies().contains(&armor.armor_type));
+            }
+        }
+    }
+
+    #[test]
+    fn wizard_kit_has_no_armor() {
+        let mut wizard = character_of(Class::Wizard);
+        wizard.choose_random_starting_equipment(3).unwrap();
+        assert!(wizard.inventory.body_armor.is_none());
+        assert!(wizard.inventory.shield.is_none());
+    }
+}
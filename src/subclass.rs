@@ -0,0 +1,1208 @@
+use crate::{class::Class, feature::Feature};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum Subclass {
+    // Barbarian - Primal Paths
+    Berserker,
+    TotemWarrior,
+    // Bard - Colleges
+    Lore,
+    Valor,
+    // Cleric - Divine Domains
+    KnowledgeDomain,
+    LifeDomain,
+    LightDomain,
+    NatureDomain,
+    TempestDomain,
+    TrickeryDomain,
+    WarDomain,
+    // Druid - Circles
+    CircleOfTheLand,
+    CircleOfTheMoon,
+    // Fighter - Martial Archetypes
+    Champion,
+    BattleMaster,
+    EldritchKnight,
+    // Monk - Monastic Traditions
+    OpenHand,
+    Shadow,
+    FourElements,
+    // Paladin - Sacred Oaths
+    Devotion,
+    Ancients,
+    Vengeance,
+    // Ranger - Archetypes
+    Hunter,
+    BeastMaster,
+    // Rogue - Archetypes
+    Thief,
+    Assassin,
+    ArcaneTrickster,
+    // Sorcerer - Origins
+    DraconicBloodline,
+    WildMagic,
+    // Warlock - Patrons
+    Archfey,
+    Fiend,
+    GreatOldOne,
+    // Wizard - Arcane Traditions
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubclassError {
+    WrongClass { subclass: Subclass, class: Class },
+    LevelTooLow { required: u8, current: u8 },
+    AlreadyChosen(Subclass),
+}
+
+impl fmt::Display for SubclassError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SubclassError::WrongClass { subclass, class } => {
+                write!(f, "{} needs levels in {}", subclass, class)
+            }
+            SubclassError::LevelTooLow { required, current } => write!(
+                f,
+                "Subclass is chosen at level {}, character is level {}",
+                required, current
+            ),
+            SubclassError::AlreadyChosen(subclass) => {
+                write!(f, "Subclass {} is already chosen", subclass)
+            }
+        }
+    }
+}
+
+impl Class {
+    /// Level at which the class picks its subclass
+    pub fn subclass_level(&self) -> u8 {
+        match self {
+            Class::Cleric | Class::Sorcerer | Class::Warlock => 1,
+            Class::Druid | Class::Wizard => 2,
+            _ => 3,
+        }
+    }
+
+    pub fn subclasses(&self) -> Vec<Subclass> {
+        Subclass::iter().filter(|s| s.class() == *self).collect()
+    }
+}
+
+impl Subclass {
+    pub fn class(&self) -> Class {
+        match self {
+            Subclass::Berserker | Subclass::TotemWarrior => Class::Barbarian,
+            Subclass::Lore | Subclass::Valor => Class::Bard,
+            Subclass::KnowledgeDomain
+            | Subclass::LifeDomain
+            | Subclass::LightDomain
+            | Subclass::NatureDomain
+            | Subclass::TempestDomain
+            | Subclass::TrickeryDomain
+            | Subclass::WarDomain => Class::Cleric,
+            Subclass::CircleOfTheLand | Subclass::CircleOfTheMoon => Class::Druid,
+            Subclass::Champion | Subclass::BattleMaster | Subclass::EldritchKnight => {
+                Class::Fighter
+            }
+            Subclass::OpenHand | Subclass::Shadow | Subclass::FourElements => Class::Monk,
+            Subclass::Devotion | Subclass::Ancients | Subclass::Vengeance => Class::Paladin,
+            Subclass::Hunter | Subclass::BeastMaster => Class::Ranger,
+            Subclass::Thief | Subclass::Assassin | Subclass::ArcaneTrickster => Class::Rogue,
+            Subclass::DraconicBloodline | Subclass::WildMagic => Class::Sorcerer,
+            Subclass::Archfey | Subclass::Fiend | Subclass::GreatOldOne => Class::Warlock,
+            Subclass::Abjuration
+            | Subclass::Conjuration
+            | Subclass::Divination
+            | Subclass::Enchantment
+            | Subclass::Evocation
+            | Subclass::Illusion
+            | Subclass::Necromancy
+            | Subclass::Transmutation => Class::Wizard,
+        }
+    }
+
+    /// Every feature the subclass grants, ordered by level
+    pub fn features(&self) -> Vec<Feature> {
+        match self {
+            Subclass::Berserker => vec![
+                Feature::new(
+                    3,
+                    "Frenzy",
+                    "Rage into a frenzy for a bonus action melee attack each turn.",
+                ),
+                Feature::new(
+                    6,
+                    "Mindless Rage",
+                    "Can't be charmed or frightened while raging.",
+                ),
+                Feature::new(
+                    10,
+                    "Intimidating Presence",
+                    "Use an action to frighten a creature.",
+                ),
+                Feature::new(
+                    14,
+                    "Retaliation",
+                    "Use a reaction to attack a creature that damages you.",
+                ),
+            ],
+            Subclass::TotemWarrior => vec![
+                Feature::new(
+                    3,
+                    "Spirit Seeker",
+                    "Cast beast sense and speak with animals as rituals.",
+                ),
+                Feature::new(
+                    3,
+                    "Totem Spirit",
+                    "Choose a bear, eagle or wolf totem spirit.",
+                ),
+                Feature::new(
+                    6,
+                    "Aspect of the Beast",
+                    "Gain a benefit from your chosen totem animal.",
+                ),
+                Feature::new(10, "Spirit Walker", "Cast commune with nature as a ritual."),
+                Feature::new(
+                    14,
+                    "Totemic Attunement",
+                    "Gain a powerful totem benefit while raging.",
+                ),
+            ],
+            Subclass::Lore => vec![
+                Feature::new(
+                    3,
+                    "Bonus Proficiencies",
+                    "Gain proficiency with three skills of your choice.",
+                ),
+                Feature::new(
+                    3,
+                    "Cutting Words",
+                    "Spend Bardic Inspiration to reduce an enemy's roll.",
+                ),
+                Feature::new(
+                    6,
+                    "Additional Magical Secrets",
+                    "Learn two spells from any class.",
+                ),
+                Feature::new(
+                    14,
+                    "Peerless Skill",
+                    "Add Bardic Inspiration to your own ability checks.",
+                ),
+            ],
+            Subclass::Valor => vec![
+                Feature::new(
+                    3,
+                    "Bonus Proficiencies",
+                    "Gain proficiency with medium armor, shields and martial weapons.",
+                ),
+                Feature::new(
+                    3,
+                    "Combat Inspiration",
+                    "Bardic Inspiration can add to damage or AC.",
+                ),
+                Feature::new(
+                    6,
+                    "Extra Attack",
+                    "Attack twice when you take the Attack action.",
+                ),
+                Feature::new(
+                    14,
+                    "Battle Magic",
+                    "Make a weapon attack as a bonus action after casting a spell.",
+                ),
+            ],
+            Subclass::KnowledgeDomain => vec![
+                Feature::new(
+                    1,
+                    "Blessings of Knowledge",
+                    "Learn two languages and gain expertise in two knowledge skills.",
+                ),
+                Feature::new(
+                    2,
+                    "Knowledge of the Ages",
+                    "Channel Divinity to gain proficiency with a skill or tool.",
+                ),
+                Feature::new(
+                    6,
+                    "Read Thoughts",
+                    "Channel Divinity to read a creature's surface thoughts.",
+                ),
+                Feature::new(
+                    8,
+                    "Potent Spellcasting",
+                    "Add WIS modifier to cleric cantrip damage.",
+                ),
+                Feature::new(
+                    17,
+                    "Visions of the Past",
+                    "Call up visions of an object's or area's past.",
+                ),
+            ],
+            Subclass::LifeDomain => vec![
+                Feature::new(1, "Bonus Proficiency", "Gain proficiency with heavy armor."),
+                Feature::new(
+                    1,
+                    "Disciple of Life",
+                    "Healing spells restore 2 + spell level extra hit points.",
+                ),
+                Feature::new(
+                    2,
+                    "Preserve Life",
+                    "Channel Divinity to split 5 x cleric level healing among creatures.",
+                ),
+                Feature::new(
+                    6,
+                    "Blessed Healer",
+                    "Heal yourself when you heal others with a spell.",
+                ),
+                Feature::new(
+                    8,
+                    "Divine Strike",
+                    "Once per turn deal an extra 1d8 radiant damage with a weapon.",
+                ),
+                Feature::new(
+                    17,
+                    "Supreme Healing",
+                    "Healing dice always roll their maximum.",
+                ),
+            ],
+            Subclass::LightDomain => vec![
+                Feature::new(1, "Bonus Cantrip", "Learn the light cantrip."),
+                Feature::new(
+                    1,
+                    "Warding Flare",
+                    "Impose disadvantage on an attack against you.",
+                ),
+                Feature::new(
+                    2,
+                    "Radiance of the Dawn",
+                    "Channel Divinity to dispel darkness and deal radiant damage.",
+                ),
+                Feature::new(
+                    6,
+                    "Improved Flare",
+                    "Warding Flare can protect other creatures.",
+                ),
+                Feature::new(
+                    8,
+                    "Potent Spellcasting",
+                    "Add WIS modifier to cleric cantrip damage.",
+                ),
+                Feature::new(
+                    17,
+                    "Corona of Light",
+                    "Emit sunlight that weakens saves against fire and radiant spells.",
+                ),
+            ],
+            Subclass::NatureDomain => vec![
+                Feature::new(
+                    1,
+                    "Acolyte of Nature",
+                    "Learn a druid cantrip and a nature skill.",
+                ),
+                Feature::new(1, "Bonus Proficiency", "Gain proficiency with heavy armor."),
+                Feature::new(
+                    2,
+                    "Charm Animals and Plants",
+                    "Channel Divinity to charm beasts and plants.",
+                ),
+                Feature::new(
+                    6,
+                    "Dampen Elements",
+                    "Grant resistance to elemental damage as a reaction.",
+                ),
+                Feature::new(
+                    8,
+                    "Divine Strike",
+                    "Once per turn deal an extra 1d8 elemental damage with a weapon.",
+                ),
+                Feature::new(
+                    17,
+                    "Master of Nature",
+                    "Command charmed animals and plants as a bonus action.",
+                ),
+            ],
+            Subclass::TempestDomain => vec![
+                Feature::new(
+                    1,
+                    "Bonus Proficiencies",
+                    "Gain proficiency with martial weapons and heavy armor.",
+                ),
+                Feature::new(
+                    1,
+                    "Wrath of the Storm",
+                    "Deal lightning or thunder damage to an attacker as a reaction.",
+                ),
+                Feature::new(
+                    2,
+                    "Destructive Wrath",
+                    "Channel Divinity to maximize lightning or thunder damage.",
+                ),
+                Feature::new(
+                    6,
+                    "Thunderbolt Strike",
+                    "Push Large or smaller creatures with lightning damage.",
+                ),
+                Feature::new(
+                    8,
+                    "Divine Strike",
+                    "Once per turn deal an extra 1d8 thunder damage with a weapon.",
+                ),
+                Feature::new(17, "Stormborn", "Gain a flying speed outdoors."),
+            ],
+            Subclass::TrickeryDomain => vec![
+                Feature::new(
+                    1,
+                    "Blessing of the Trickster",
+                    "Grant advantage on Stealth checks.",
+                ),
+                Feature::new(
+                    2,
+                    "Invoke Duplicity",
+                    "Channel Divinity to create an illusory duplicate.",
+                ),
+                Feature::new(
+                    6,
+                    "Cloak of Shadows",
+                    "Channel Divinity to become invisible.",
+                ),
+                Feature::new(
+                    8,
+                    "Divine Strike",
+                    "Once per turn deal an extra 1d8 poison damage with a weapon.",
+                ),
+                Feature::new(17, "Improved Duplicity", "Create up to four duplicates."),
+            ],
+            Subclass::WarDomain => vec![
+                Feature::new(
+                    1,
+                    "Bonus Proficiencies",
+                    "Gain proficiency with martial weapons and heavy armor.",
+                ),
+                Feature::new(1, "War Priest", "Make a weapon attack as a bonus action."),
+                Feature::new(
+                    2,
+                    "Guided Strike",
+                    "Channel Divinity to gain +10 to an attack roll.",
+                ),
+                Feature::new(
+                    6,
+                    "War God's Blessing",
+                    "Channel Divinity to grant +10 to an ally's attack roll.",
+                ),
+                Feature::new(
+                    8,
+                    "Divine Strike",
+                    "Once per turn deal an extra 1d8 weapon damage.",
+                ),
+                Feature::new(
+                    17,
+                    "Avatar of Battle",
+                    "Gain resistance to nonmagical weapon damage.",
+                ),
+            ],
+            Subclass::CircleOfTheLand => vec![
+                Feature::new(2, "Bonus Cantrip", "Learn one additional druid cantrip."),
+                Feature::new(
+                    2,
+                    "Natural Recovery",
+                    "Recover spell slots during a short rest.",
+                ),
+                Feature::new(
+                    3,
+                    "Circle Spells",
+                    "Gain always prepared spells tied to your chosen land.",
+                ),
+                Feature::new(
+                    6,
+                    "Land's Stride",
+                    "Move through nonmagical difficult terrain freely.",
+                ),
+                Feature::new(
+                    10,
+                    "Nature's Ward",
+                    "Immune to poison, disease, and charm or fear from fey and elementals.",
+                ),
+                Feature::new(
+                    14,
+                    "Nature's Sanctuary",
+                    "Beasts and plants must save to attack you.",
+                ),
+            ],
+            Subclass::CircleOfTheMoon => vec![
+                Feature::new(
+                    2,
+                    "Combat Wild Shape",
+                    "Wild Shape as a bonus action and heal with spell slots.",
+                ),
+                Feature::new(
+                    2,
+                    "Circle Forms",
+                    "Wild Shape into beasts of challenge rating 1.",
+                ),
+                Feature::new(6, "Primal Strike", "Beast form attacks count as magical."),
+                Feature::new(
+                    10,
+                    "Elemental Wild Shape",
+                    "Spend two Wild Shape uses to become an elemental.",
+                ),
+                Feature::new(14, "Thousand Forms", "Cast alter self at will."),
+            ],
+            Subclass::Champion => vec![
+                Feature::new(
+                    3,
+                    "Improved Critical",
+                    "Weapon attacks score a critical hit on a 19 or 20.",
+                ),
+                Feature::new(
+                    7,
+                    "Remarkable Athlete",
+                    "Add half proficiency to untrained STR, DEX and CON checks.",
+                ),
+                Feature::new(
+                    10,
+                    "Additional Fighting Style",
+                    "Choose a second Fighting Style.",
+                ),
+                Feature::new(
+                    15,
+                    "Superior Critical",
+                    "Weapon attacks score a critical hit on an 18-20.",
+                ),
+                Feature::new(
+                    18,
+                    "Survivor",
+                    "Regain hit points each turn while below half hit points.",
+                ),
+            ],
+            Subclass::BattleMaster => vec![
+                Feature::new(
+                    3,
+                    "Combat Superiority",
+                    "Learn maneuvers fueled by superiority dice.",
+                ),
+                Feature::new(
+                    3,
+                    "Student of War",
+                    "Gain proficiency with one artisan's tool.",
+                ),
+                Feature::new(
+                    7,
+                    "Know Your Enemy",
+                    "Study a creature to learn how it compares to you.",
+                ),
+                Feature::new(
+                    10,
+                    "Improved Combat Superiority",
+                    "Superiority dice become d10s.",
+                ),
+                Feature::new(
+                    15,
+                    "Relentless",
+                    "Regain a superiority die when rolling initiative with none left.",
+                ),
+                Feature::new(
+                    18,
+                    "Improved Combat Superiority",
+                    "Superiority dice become d12s.",
+                ),
+            ],
+            Subclass::EldritchKnight => vec![
+                Feature::new(3, "Spellcasting", "Cast wizard spells using INT."),
+                Feature::new(
+                    3,
+                    "Weapon Bond",
+                    "Bond with up to two weapons that can't be disarmed.",
+                ),
+                Feature::new(
+                    7,
+                    "War Magic",
+                    "Make a weapon attack as a bonus action after casting a cantrip.",
+                ),
+                Feature::new(
+                    10,
+                    "Eldritch Strike",
+                    "Weapon hits impose disadvantage on saves against your next spell.",
+                ),
+                Feature::new(15, "Arcane Charge", "Teleport when you use Action Surge."),
+                Feature::new(
+                    18,
+                    "Improved War Magic",
+                    "Make a weapon attack as a bonus action after casting a spell.",
+                ),
+            ],
+            Subclass::OpenHand => vec![
+                Feature::new(
+                    3,
+                    "Open Hand Technique",
+                    "Flurry of Blows can knock prone, push or deny reactions.",
+                ),
+                Feature::new(
+                    6,
+                    "Wholeness of Body",
+                    "Regain hit points equal to three times your monk level.",
+                ),
+                Feature::new(
+                    11,
+                    "Tranquility",
+                    "Gain the effect of sanctuary after a long rest.",
+                ),
+                Feature::new(
+                    17,
+                    "Quivering Palm",
+                    "Set up lethal vibrations in a creature's body.",
+                ),
+            ],
+            Subclass::Shadow => vec![
+                Feature::new(
+                    3,
+                    "Shadow Arts",
+                    "Spend ki to cast darkness, darkvision, pass without trace or silence.",
+                ),
+                Feature::new(
+                    6,
+                    "Shadow Step",
+                    "Teleport between areas of dim light or darkness.",
+                ),
+                Feature::new(
+                    11,
+                    "Cloak of Shadows",
+                    "Become invisible in dim light or darkness.",
+                ),
+                Feature::new(
+                    17,
+                    "Opportunist",
+                    "Attack a creature hit by another creature as a reaction.",
+                ),
+            ],
+            Subclass::FourElements => vec![
+                Feature::new(
+                    3,
+                    "Disciple of the Elements",
+                    "Learn elemental disciplines fueled by ki.",
+                ),
+                Feature::new(
+                    6,
+                    "Elemental Discipline",
+                    "Learn an additional elemental discipline.",
+                ),
+                Feature::new(
+                    11,
+                    "Elemental Discipline",
+                    "Learn an additional elemental discipline.",
+                ),
+                Feature::new(
+                    17,
+                    "Elemental Discipline",
+                    "Learn an additional elemental discipline.",
+                ),
+            ],
+            Subclass::Devotion => vec![
+                Feature::new(
+                    3,
+                    "Sacred Weapon",
+                    "Channel Divinity to add CHA modifier to weapon attacks.",
+                ),
+                Feature::new(
+                    3,
+                    "Turn the Unholy",
+                    "Channel Divinity to turn fiends and undead.",
+                ),
+                Feature::new(
+                    7,
+                    "Aura of Devotion",
+                    "You and nearby allies can't be charmed.",
+                ),
+                Feature::new(
+                    15,
+                    "Purity of Spirit",
+                    "Always under the effect of protection from evil and good.",
+                ),
+                Feature::new(20, "Holy Nimbus", "Emanate sunlight that damages enemies."),
+            ],
+            Subclass::Ancients => vec![
+                Feature::new(
+                    3,
+                    "Nature's Wrath",
+                    "Channel Divinity to restrain a creature with vines.",
+                ),
+                Feature::new(
+                    3,
+                    "Turn the Faithless",
+                    "Channel Divinity to turn fey and fiends.",
+                ),
+                Feature::new(
+                    7,
+                    "Aura of Warding",
+                    "You and nearby allies resist spell damage.",
+                ),
+                Feature::new(
+                    15,
+                    "Undying Sentinel",
+                    "Drop to 1 hit point instead of 0 once per long rest.",
+                ),
+                Feature::new(20, "Elder Champion", "Transform into a force of nature."),
+            ],
+            Subclass::Vengeance => vec![
+                Feature::new(
+                    3,
+                    "Abjure Enemy",
+                    "Channel Divinity to frighten a creature.",
+                ),
+                Feature::new(
+                    3,
+                    "Vow of Enmity",
+                    "Channel Divinity to gain advantage against a creature.",
+                ),
+                Feature::new(
+                    7,
+                    "Relentless Avenger",
+                    "Move after an opportunity attack hits.",
+                ),
+                Feature::new(
+                    15,
+                    "Soul of Vengeance",
+                    "Attack your Vow of Enmity target as a reaction.",
+                ),
+                Feature::new(
+                    20,
+                    "Avenging Angel",
+                    "Sprout wings and emanate a menacing aura.",
+                ),
+            ],
+            Subclass::Hunter => vec![
+                Feature::new(
+                    3,
+                    "Hunter's Prey",
+                    "Choose Colossus Slayer, Giant Killer or Horde Breaker.",
+                ),
+                Feature::new(
+                    7,
+                    "Defensive Tactics",
+                    "Choose Escape the Horde, Multiattack Defense or Steel Will.",
+                ),
+                Feature::new(11, "Multiattack", "Choose Volley or Whirlwind Attack."),
+                Feature::new(
+                    15,
+                    "Superior Hunter's Defense",
+                    "Choose Evasion, Stand Against the Tide or Uncanny Dodge.",
+                ),
+            ],
+            Subclass::BeastMaster => vec![
+                Feature::new(
+                    3,
+                    "Ranger's Companion",
+                    "Gain a beast companion that fights alongside you.",
+                ),
+                Feature::new(
+                    7,
+                    "Exceptional Training",
+                    "Command your companion to Dash, Disengage, Dodge or Help.",
+                ),
+                Feature::new(11, "Bestial Fury", "Your companion attacks twice."),
+                Feature::new(
+                    15,
+                    "Share Spells",
+                    "Self-targeted spells also affect your companion.",
+                ),
+            ],
+            Subclass::Thief => vec![
+                Feature::new(
+                    3,
+                    "Fast Hands",
+                    "Use Cunning Action for Sleight of Hand, thieves' tools or Use an Object.",
+                ),
+                Feature::new(
+                    3,
+                    "Second-Story Work",
+                    "Climb at full speed and jump farther.",
+                ),
+                Feature::new(
+                    9,
+                    "Supreme Sneak",
+                    "Advantage on Stealth when moving at half speed.",
+                ),
+                Feature::new(
+                    13,
+                    "Use Magic Device",
+                    "Ignore class, race and level requirements on magic items.",
+                ),
+                Feature::new(
+                    17,
+                    "Thief's Reflexes",
+                    "Take two turns during the first round of combat.",
+                ),
+            ],
+            Subclass::Assassin => vec![
+                Feature::new(
+                    3,
+                    "Bonus Proficiencies",
+                    "Gain proficiency with the disguise kit and poisoner's kit.",
+                ),
+                Feature::new(
+                    3,
+                    "Assassinate",
+                    "Advantage against creatures that haven't acted; hits on surprised creatures are critical.",
+                ),
+                Feature::new(9, "Infiltration Expertise", "Create false identities."),
+                Feature::new(
+                    13,
+                    "Impostor",
+                    "Mimic another person's speech, writing and behavior.",
+                ),
+                Feature::new(
+                    17,
+                    "Death Strike",
+                    "Double damage against surprised creatures that fail a CON save.",
+                ),
+            ],
+            Subclass::ArcaneTrickster => vec![
+                Feature::new(3, "Spellcasting", "Cast wizard spells using INT."),
+                Feature::new(
+                    3,
+                    "Mage Hand Legerdemain",
+                    "Your mage hand is invisible and can pick locks and pockets.",
+                ),
+                Feature::new(
+                    9,
+                    "Magical Ambush",
+                    "Creatures have disadvantage on saves against spells cast while hidden.",
+                ),
+                Feature::new(
+                    13,
+                    "Versatile Trickster",
+                    "Use mage hand to gain advantage on attacks.",
+                ),
+                Feature::new(17, "Spell Thief", "Steal a spell cast at you."),
+            ],
+            Subclass::DraconicBloodline => vec![
+                Feature::new(
+                    1,
+                    "Dragon Ancestor",
+                    "Choose a dragon ancestry and speak Draconic.",
+                ),
+                Feature::new(
+                    1,
+                    "Draconic Resilience",
+                    "Gain extra hit points and AC 13 + DEX unarmored.",
+                ),
+                Feature::new(
+                    6,
+                    "Elemental Affinity",
+                    "Add CHA modifier to damage of your ancestry's type.",
+                ),
+                Feature::new(
+                    14,
+                    "Dragon Wings",
+                    "Sprout dragon wings and gain a flying speed.",
+                ),
+                Feature::new(
+                    18,
+                    "Draconic Presence",
+                    "Spend sorcery points to charm or frighten nearby creatures.",
+                ),
+            ],
+            Subclass::WildMagic => vec![
+                Feature::new(
+                    1,
+                    "Wild Magic Surge",
+                    "Casting spells can trigger random magical effects.",
+                ),
+                Feature::new(
+                    1,
+                    "Tides of Chaos",
+                    "Gain advantage on one roll per long rest.",
+                ),
+                Feature::new(
+                    6,
+                    "Bend Luck",
+                    "Spend sorcery points to add or subtract 1d4 from a roll.",
+                ),
+                Feature::new(
+                    14,
+                    "Controlled Chaos",
+                    "Roll twice on the Wild Magic Surge table.",
+                ),
+                Feature::new(
+                    18,
+                    "Spell Bombardment",
+                    "Reroll and add maximum damage dice.",
+                ),
+            ],
+            Subclass::Archfey => vec![
+                Feature::new(1, "Fey Presence", "Charm or frighten nearby creatures."),
+                Feature::new(
+                    6,
+                    "Misty Escape",
+                    "Turn invisible and teleport when damaged.",
+                ),
+                Feature::new(
+                    10,
+                    "Beguiling Defenses",
+                    "Immune to being charmed and can turn charms back.",
+                ),
+                Feature::new(
+                    14,
+                    "Dark Delirium",
+                    "Plunge a creature into an illusory realm.",
+                ),
+            ],
+            Subclass::Fiend => vec![
+                Feature::new(
+                    1,
+                    "Dark One's Blessing",
+                    "Gain temporary hit points when you reduce a hostile creature to 0.",
+                ),
+                Feature::new(
+                    6,
+                    "Dark One's Own Luck",
+                    "Add 1d10 to an ability check or saving throw.",
+                ),
+                Feature::new(
+                    10,
+                    "Fiendish Resilience",
+                    "Choose a damage type to resist after a rest.",
+                ),
+                Feature::new(
+                    14,
+                    "Hurl Through Hell",
+                    "Send a creature through the lower planes for 10d10 psychic damage.",
+                ),
+            ],
+            Subclass::GreatOldOne => vec![
+                Feature::new(
+                    1,
+                    "Awakened Mind",
+                    "Speak telepathically with creatures within 30 feet.",
+                ),
+                Feature::new(
+                    6,
+                    "Entropic Ward",
+                    "Impose disadvantage on an attack and gain advantage if it misses.",
+                ),
+                Feature::new(
+                    10,
+                    "Thought Shield",
+                    "Resist psychic damage and keep your thoughts unreadable.",
+                ),
+                Feature::new(
+                    14,
+                    "Create Thrall",
+                    "Charm an incapacitated humanoid indefinitely.",
+                ),
+            ],
+            Subclass::Abjuration => vec![
+                Feature::new(
+                    2,
+                    "Abjuration Savant",
+                    "Copy abjuration spells for half the cost.",
+                ),
+                Feature::new(2, "Arcane Ward", "Create a ward that absorbs damage."),
+                Feature::new(
+                    6,
+                    "Projected Ward",
+                    "Use your Arcane Ward to protect an ally.",
+                ),
+                Feature::new(
+                    10,
+                    "Improved Abjuration",
+                    "Add proficiency to abjuration ability checks.",
+                ),
+                Feature::new(
+                    14,
+                    "Spell Resistance",
+                    "Advantage on saves against spells and resistance to spell damage.",
+                ),
+            ],
+            Subclass::Conjuration => vec![
+                Feature::new(
+                    2,
+                    "Conjuration Savant",
+                    "Copy conjuration spells for half the cost.",
+                ),
+                Feature::new(2, "Minor Conjuration", "Conjure a small nonmagical object."),
+                Feature::new(
+                    6,
+                    "Benign Transposition",
+                    "Teleport or swap places with a creature.",
+                ),
+                Feature::new(
+                    10,
+                    "Focused Conjuration",
+                    "Damage can't break concentration on conjuration spells.",
+                ),
+                Feature::new(
+                    14,
+                    "Durable Summons",
+                    "Summoned creatures gain 30 temporary hit points.",
+                ),
+            ],
+            Subclass::Divination => vec![
+                Feature::new(
+                    2,
+                    "Divination Savant",
+                    "Copy divination spells for half the cost.",
+                ),
+                Feature::new(
+                    2,
+                    "Portent",
+                    "Roll two d20s after a long rest to replace future rolls.",
+                ),
+                Feature::new(
+                    6,
+                    "Expert Divination",
+                    "Regain a spell slot when casting divination spells.",
+                ),
+                Feature::new(
+                    10,
+                    "The Third Eye",
+                    "Gain darkvision, ethereal sight, comprehension or see invisibility.",
+                ),
+                Feature::new(14, "Greater Portent", "Roll three d20s for Portent."),
+            ],
+            Subclass::Enchantment => vec![
+                Feature::new(
+                    2,
+                    "Enchantment Savant",
+                    "Copy enchantment spells for half the cost.",
+                ),
+                Feature::new(2, "Hypnotic Gaze", "Charm a creature with your gaze."),
+                Feature::new(
+                    6,
+                    "Instinctive Charm",
+                    "Redirect an attack to another creature.",
+                ),
+                Feature::new(
+                    10,
+                    "Split Enchantment",
+                    "Single-target enchantments can target a second creature.",
+                ),
+                Feature::new(
+                    14,
+                    "Alter Memories",
+                    "Make a charmed creature forget it was charmed.",
+                ),
+            ],
+            Subclass::Evocation => vec![
+                Feature::new(
+                    2,
+                    "Evocation Savant",
+                    "Copy evocation spells for half the cost.",
+                ),
+                Feature::new(
+                    2,
+                    "Sculpt Spells",
+                    "Protect allies from your evocation spells.",
+                ),
+                Feature::new(
+                    6,
+                    "Potent Cantrip",
+                    "Creatures that save against your cantrips take half damage.",
+                ),
+                Feature::new(
+                    10,
+                    "Empowered Evocation",
+                    "Add INT modifier to evocation spell damage.",
+                ),
+                Feature::new(
+                    14,
+                    "Overchannel",
+                    "Deal maximum damage with a low-level spell.",
+                ),
+            ],
+            Subclass::Illusion => vec![
+                Feature::new(
+                    2,
+                    "Illusion Savant",
+                    "Copy illusion spells for half the cost.",
+                ),
+                Feature::new(
+                    2,
+                    "Improved Minor Illusion",
+                    "Minor illusion creates both sound and image.",
+                ),
+                Feature::new(
+                    6,
+                    "Malleable Illusions",
+                    "Change the nature of your illusions.",
+                ),
+                Feature::new(
+                    10,
+                    "Illusory Self",
+                    "Create an illusory duplicate to make an attack miss.",
+                ),
+                Feature::new(14, "Illusory Reality", "Make part of an illusion real."),
+            ],
+            Subclass::Necromancy => vec![
+                Feature::new(
+                    2,
+                    "Necromancy Savant",
+                    "Copy necromancy spells for half the cost.",
+                ),
+                Feature::new(
+                    2,
+                    "Grim Harvest",
+                    "Regain hit points when your spells kill a creature.",
+                ),
+                Feature::new(
+                    6,
+                    "Undead Thralls",
+                    "Animate dead creates an extra, stronger undead.",
+                ),
+                Feature::new(
+                    10,
+                    "Inured to Undeath",
+                    "Resist necrotic damage and your hit point maximum can't be reduced.",
+                ),
+                Feature::new(14, "Command Undead", "Take control of an undead creature."),
+            ],
+            Subclass::Transmutation => vec![
+                Feature::new(
+                    2,
+                    "Transmutation Savant",
+                    "Copy transmutation spells for half the cost.",
+                ),
+                Feature::new(
+                    2,
+                    "Minor Alchemy",
+                    "Temporarily change the material of an object.",
+                ),
+                Feature::new(
+                    6,
+                    "Transmuter's Stone",
+                    "Create a stone that grants a magical benefit.",
+                ),
+                Feature::new(
+                    10,
+                    "Shapechanger",
+                    "Cast polymorph on yourself without a slot.",
+                ),
+                Feature::new(
+                    14,
+                    "Master Transmuter",
+                    "Consume your stone for a powerful effect.",
+                ),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for Subclass {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let subclass = match self {
+            Subclass::Berserker => "Path of the Berserker",
+            Subclass::TotemWarrior => "Path of the Totem Warrior",
+            Subclass::Lore => "College of Lore",
+            Subclass::Valor => "College of Valor",
+            Subclass::KnowledgeDomain => "Knowledge Domain",
+            Subclass::LifeDomain => "Life Domain",
+            Subclass::LightDomain => "Light Domain",
+            Subclass::NatureDomain => "Nature Domain",
+            Subclass::TempestDomain => "Tempest Domain",
+            Subclass::TrickeryDomain => "Trickery Domain",
+            Subclass::WarDomain => "War Domain",
+            Subclass::CircleOfTheLand => "Circle of the Land",
+            Subclass::CircleOfTheMoon => "Circle of the Moon",
+            Subclass::Champion => "Champion",
+            Subclass::BattleMaster => "Battle Master",
+            Subclass::EldritchKnight => "Eldritch Knight",
+            Subclass::OpenHand => "Way of the Open Hand",
+            Subclass::Shadow => "Way of Shadow",
+            Subclass::FourElements => "Way of the Four Elements",
+            Subclass::Devotion => "Oath of Devotion",
+            Subclass::Ancients => "Oath of the Ancients",
+            Subclass::Vengeance => "Oath of Vengeance",
+            Subclass::Hunter => "Hunter",
+            Subclass::BeastMaster => "Beast Master",
+            Subclass::Thief => "Thief",
+            Subclass::Assassin => "Assassin",
+            Subclass::ArcaneTrickster => "Arcane Trickster",
+            Subclass::DraconicBloodline => "Draconic Bloodline",
+            Subclass::WildMagic => "Wild Magic",
+            Subclass::Archfey => "The Archfey",
+            Subclass::Fiend => "The Fiend",
+            Subclass::GreatOldOne => "The Great Old One",
+            Subclass::Abjuration => "School of Abjuration",
+            Subclass::Conjuration => "School of Conjuration",
+            Subclass::Divination => "School of Divination",
+            Subclass::Enchantment => "School of Enchantment",
+            Subclass::Evocation => "School of Evocation",
+            Subclass::Illusion => "School of Illusion",
+            Subclass::Necromancy => "School of Necromancy",
+            Subclass::Transmutation => "School of Transmutation",
+        };
+        write!(f, "{}", subclass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{character_of, set_levels};
+
+    #[test]
+    fn subclass_waits_for_the_class_level() {
+        let mut fighter = character_of(Class::Fighter);
+        set_levels(&mut fighter, &[(Class::Fighter, 2)]);
+        assert_eq!(
+            fighter.choose_subclass(Subclass::Champion),
+            Err(SubclassError::LevelTooLow {
+                required: 3,
+                current: 2
+            })
+        );
+        assert_eq!(
+            fighter.choose_subclass(Subclass::Thief),
+            Err(SubclassError::WrongClass {
+                subclass: Subclass::Thief,
+                class: Class::Rogue
+            })
+        );
+
+        set_levels(&mut fighter, &[(Class::Fighter, 3)]);
+        assert_eq!(fighter.choose_subclass(Subclass::Champion), Ok(()));
+        assert_eq!(
+            fighter.choose_subclass(Subclass::BattleMaster),
+            Err(SubclassError::AlreadyChosen(Subclass::Champion))
+        );
+    }
+
+    #[test]
+    fn subclass_features_unlock_with_class_levels() {
+        let mut fighter = character_of(Class::Fighter);
+        set_levels(&mut fighter, &[(Class::Fighter, 7)]);
+        fighter.choose_subclass(Subclass::Champion).unwrap();
+
+        let names: Vec<&str> = fighter
+            .subclass_features()
+            .iter()
+            .map(|feature| feature.name)
+            .collect();
+        assert_eq!(names, vec!["Improved Critical", "Remarkable Athlete"]);
+    }
+
+    #[test]
+    fn subclass_shows_on_the_sheet_and_in_json() {
+        let mut fighter = character_of(Class::Fighter);
+        set_levels(&mut fighter, &[(Class::Fighter, 3)]);
+        fighter.choose_subclass(Subclass::Champion).unwrap();
+
+        assert!(fighter.to_string().contains("FIGHTER (CHAMPION)"));
+        let json = serde_json::to_string(&fighter).unwrap();
+        assert!(json.contains("\"subclass\":\"Champion\""));
+    }
+}
@@ -0,0 +1,60 @@
+//! Fixtures shared by the unit tests
+
+use crate::{
+    ability::Abilities, background::Background, character::Character, class::Class,
+    multiclass::ClassLevel, pact_magic::PactSlots, race::Race, spellcasting::SpellSlots,
+};
+
+/// A new character with every ability at 10, so no modifier skews the numbers
+pub fn character(race: Race, class: Class, background: Background) -> Character {
+    let mut character = Character::new("Test".to_string(), race, class, background).unwrap();
+    set_abilities(&mut character, 10);
+    character
+}
+
+/// A Human Soldier of `class`
+pub fn character_of(class: Class) -> Character {
+    character(Race::Human, class, Background::Soldier)
+}
+
+/// Sets every ability to `score` and recalculates the hit points
+pub fn set_abilities(character: &mut Character, score: u8) {
+    for ability in [
+        Abilities::Strength,
+        Abilities::Dexterity,
+        Abilities::Constitution,
+        Abilities::Intelligence,
+        Abilities::Wisdom,
+        Abilities::Charisma,
+    ] {
+        character.stats.set(ability, score);
+    }
+    character.calculate_max_hp();
+    character.current_hp = character.max_hp;
+}
+
+/// Replaces the class levels, each level rolling the maximum of its hit die,
+/// and rebuilds HP, hit dice, slots and resources from scratch
+pub fn set_levels(character: &mut Character, levels: &[(Class, u8)]) {
+    character.classes = levels
+        .iter()
+        .map(|(class, level)| {
+            let sides = class.get_class_hit_dice().dice_sides;
+            ClassLevel {
+                level: *level,
+                hp_rolls: vec![sides; *level as usize],
+                ..ClassLevel::new(*class, sides)
+            }
+        })
+        .collect();
+    character.calculate_max_hp();
+    character.current_hp = character.max_hp;
+    character.hit_dice.clear();
+    character.refresh_hit_dice();
+    character.spell_slots = SpellSlots::default();
+    character.refresh_spell_slots();
+    character.pact_slots = PactSlots::default();
+    character.refresh_pact_slots();
+    character.resources.clear();
+    character.refresh_resources();
+}
@@ -0,0 +1,186 @@
+use crate::ability::Abilities;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Tool {
+    // Artisan's tools
+    AlchemistsSupplies,
+    BrewersSupplies,
+    CalligraphersSupplies,
+    CarpentersTools,
+    CartographersTools,
+    CobblersTools,
+    CooksUtensils,
+    GlassblowersTools,
+    JewelersTools,
+    LeatherworkersTools,
+    MasonsTools,
+    PaintersSupplies,
+    PottersTools,
+    SmithsTools,
+    TinkersTools,
+    WeaversTools,
+    WoodcarversTools,
+    // Kits
+    DisguiseKit,
+    ForgeryKit,
+    HerbalismKit,
+    NavigatorsTools,
+    PoisonersKit,
+    ThievesTools,
+    // Gaming sets
+    DiceSet,
+    DragonchessSet,
+    PlayingCardSet,
+    ThreeDragonAnteSet,
+    // Musical instruments
+    Bagpipes,
+    Drum,
+    Dulcimer,
+    Flute,
+    Lute,
+    Lyre,
+    Horn,
+    PanFlute,
+    Shawm,
+    Viol,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCategory {
+    ArtisansTools,
+    Kit,
+    GamingSet,
+    MusicalInstrument,
+}
+
+impl Tool {
+    pub fn category(&self) -> ToolCategory {
+        match self {
+            Tool::AlchemistsSupplies
+            | Tool::BrewersSupplies
+            | Tool::CalligraphersSupplies
+            | Tool::CarpentersTools
+            | Tool::CartographersTools
+            | Tool::CobblersTools
+            | Tool::CooksUtensils
+            | Tool::GlassblowersTools
+            | Tool::JewelersTools
+            | Tool::LeatherworkersTools
+            | Tool::MasonsTools
+            | Tool::PaintersSupplies
+            | Tool::PottersTools
+            | Tool::SmithsTools
+            | Tool::TinkersTools
+            | Tool::WeaversTools
+            | Tool::WoodcarversTools => ToolCategory::ArtisansTools,
+            Tool::DisguiseKit
+            | Tool::ForgeryKit
+            | Tool::HerbalismKit
+            | Tool::NavigatorsTools
+            | Tool::PoisonersKit
+            | Tool::ThievesTools => ToolCategory::Kit,
+            Tool::DiceSet
+            | Tool::DragonchessSet
+            | Tool::PlayingCardSet
+            | Tool::ThreeDragonAnteSet => ToolCategory::GamingSet,
+            Tool::Bagpipes
+            | Tool::Drum
+            | Tool::Dulcimer
+            | Tool::Flute
+            | Tool::Lute
+            | Tool::Lyre
+            | Tool::Horn
+            | Tool::PanFlute
+            | Tool::Shawm
+            | Tool::Viol => ToolCategory::MusicalInstrument,
+        }
+    }
+
+    pub fn of_category(category: ToolCategory) -> Vec<Tool> {
+        Tool::iter()
+            .filter(|tool| tool.category() == category)
+            .collect()
+    }
+
+    /// Ability most tool checks are made with, the DM may call for another
+    pub fn ability(&self) -> Abilities {
+        match self {
+            Tool::CarpentersTools | Tool::MasonsTools | Tool::SmithsTools => Abilities::Strength,
+            Tool::AlchemistsSupplies
+            | Tool::BrewersSupplies
+            | Tool::CalligraphersSupplies
+            | Tool::CartographersTools
+            | Tool::PoisonersKit => Abilities::Intelligence,
+            Tool::CooksUtensils
+            | Tool::HerbalismKit
+            | Tool::NavigatorsTools
+            | Tool::DiceSet
+            | Tool::DragonchessSet
+            | Tool::PlayingCardSet
+            | Tool::ThreeDragonAnteSet => Abilities::Wisdom,
+            Tool::DisguiseKit => Abilities::Charisma,
+            Tool::CobblersTools
+            | Tool::GlassblowersTools
+            | Tool::JewelersTools
+            | Tool::LeatherworkersTools
+            | Tool::PaintersSupplies
+            | Tool::PottersTools
+            | Tool::TinkersTools
+            | Tool::WeaversTools
+            | Tool::WoodcarversTools
+            | Tool::ForgeryKit
+            | Tool::ThievesTools => Abilities::Dexterity,
+            // Musical instruments
+            _ => Abilities::Charisma,
+        }
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let tool = match self {
+            Tool::AlchemistsSupplies => "Alchemist's Supplies",
+            Tool::BrewersSupplies => "Brewer's Supplies",
+            Tool::CalligraphersSupplies => "Calligrapher's Supplies",
+            Tool::CarpentersTools => "Carpenter's Tools",
+            Tool::CartographersTools => "Cartographer's Tools",
+            Tool::CobblersTools => "Cobbler's Tools",
+            Tool::CooksUtensils => "Cook's Utensils",
+            Tool::GlassblowersTools => "Glassblower's Tools",
+            Tool::JewelersTools => "Jeweler's Tools",
+            Tool::LeatherworkersTools => "Leatherworker's Tools",
+            Tool::MasonsTools => "Mason's Tools",
+            Tool::PaintersSupplies => "Painter's Supplies",
+            Tool::PottersTools => "Potter's Tools",
+            Tool::SmithsTools => "Smith's Tools",
+            Tool::TinkersTools => "Tinker's Tools",
+            Tool::WeaversTools => "Weaver's Tools",
+            Tool::WoodcarversTools => "Woodcarver's Tools",
+            Tool::DisguiseKit => "Disguise Kit",
+            Tool::ForgeryKit => "Forgery Kit",
+            Tool::HerbalismKit => "Herbalism Kit",
+            Tool::NavigatorsTools => "Navigator's Tools",
+            Tool::PoisonersKit => "Poisoner's Kit",
+            Tool::ThievesTools => "Thieves' Tools",
+            Tool::DiceSet => "Dice Set",
+            Tool::DragonchessSet => "Dragonchess Set",
+            Tool::PlayingCardSet => "Playing Card Set",
+            Tool::ThreeDragonAnteSet => "Three-Dragon Ante Set",
+            Tool::Bagpipes => "Bagpipes",
+            Tool::Drum => "Drum",
+            Tool::Dulcimer => "Dulcimer",
+            Tool::Flute => "Flute",
+            Tool::Lute => "Lute",
+            Tool::Lyre => "Lyre",
+            Tool::Horn => "Horn",
+            Tool::PanFlute => "Pan Flute",
+            Tool::Shawm => "Shawm",
+            Tool::Viol => "Viol",
+        };
+        write!(f, "{}", tool)
+    }
+}